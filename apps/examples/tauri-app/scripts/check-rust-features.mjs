@@ -0,0 +1,47 @@
+import { readFileSync } from "node:fs";
+import { spawnSync } from "node:child_process";
+import { dirname, join } from "node:path";
+import { fileURLToPath } from "node:url";
+
+const here = dirname(fileURLToPath(import.meta.url));
+const tauriDir = join(here, "..", "src-tauri");
+
+// Optional host subsystems are the non-default entries of `[features]` in Cargo.toml.
+const manifest = readFileSync(join(tauriDir, "Cargo.toml"), "utf8");
+const featuresTable = manifest.split(/^\[features\]\s*$/m)[1]?.split(/^\[/m)[0] ?? "";
+const features = [...featuresTable.matchAll(/^([A-Za-z0-9_-]+)\s*=/gm)]
+  .map((match) => match[1])
+  .filter((name) => name !== "default");
+if (features.length === 0) {
+  throw new Error(`[check-rust-features] No features found in ${join(tauriDir, "Cargo.toml")}`);
+}
+
+// Defaults, nothing, each subsystem alone, and each subsystem left out.
+const combinations = [
+  { label: "default", args: [] },
+  { label: "no-default-features", args: ["--no-default-features"] },
+  ...features.map((feature) => ({
+    label: `only ${feature}`,
+    args: ["--no-default-features", "--features", feature],
+  })),
+  ...features.map((feature) => ({
+    label: `without ${feature}`,
+    args: ["--no-default-features", "--features", features.filter((other) => other !== feature).join(",")],
+  })),
+];
+
+const failed = [];
+for (const { label, args } of combinations) {
+  console.log(`[check-rust-features] ${label}`);
+  const result = spawnSync("cargo", ["clippy", "--all-targets", ...args, "--", "-D", "warnings"], {
+    cwd: tauriDir,
+    stdio: "inherit",
+  });
+  if (result.status !== 0) {
+    failed.push(label);
+  }
+}
+
+if (failed.length > 0) {
+  throw new Error(`[check-rust-features] Failing feature combinations:\n${failed.join("\n")}`);
+}
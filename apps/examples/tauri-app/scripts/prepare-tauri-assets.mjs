@@ -31,6 +31,7 @@ writeFileSync(
   generated.rustInvokeHandlersSource,
   "utf8",
 );
+writeFileSync(join(rustSrcDir, "bridge_commands_generated.rs"), generated.rustCommandsSource, "utf8");
 
 const permissionsDir = join(appRoot, "src-tauri", "permissions", "autogenerated");
 rmSync(permissionsDir, { recursive: true, force: true });
@@ -0,0 +1,25 @@
+import { spawnSync } from "node:child_process";
+import { dirname, join } from "node:path";
+import { fileURLToPath } from "node:url";
+
+const here = dirname(fileURLToPath(import.meta.url));
+const tauriDir = join(here, "..", "src-tauri");
+const checkMode = process.argv.includes("--check");
+
+// src-tauri/src/tauri_bindings.rs renders src/lib/tauriBindings.generated.ts in a
+// test; with CODEX_TAURI_BINDINGS_WRITE=1 it rewrites the file, otherwise it fails
+// while the file is stale.
+const env = checkMode ? process.env : { ...process.env, CODEX_TAURI_BINDINGS_WRITE: "1" };
+const result = spawnSync("cargo", ["test", "--bin", "codex-local-tauri-example", "tauri_bindings::"], {
+  cwd: tauriDir,
+  env,
+  stdio: "inherit",
+});
+
+if (result.status !== 0) {
+  throw new Error(
+    checkMode
+      ? "[tauri-bindings] src/lib/tauriBindings.generated.ts is stale or the host failed to build. Run `pnpm run tauri:bindings` and commit the regenerated bindings."
+      : "[tauri-bindings] Failed to regenerate src/lib/tauriBindings.generated.ts; see the cargo output above.",
+  );
+}
@@ -57,7 +57,19 @@ type HelperEvent =
   | { type: "global"; payload: Record<string, unknown> }
   | { type: "protocol_error"; payload: { message: string; line: string } }
   | { type: "ack"; payload: { command: string } }
-  | { type: "error"; payload: { message: string } };
+  | { type: "error"; payload: { message: string; command?: string; stage?: StartStage } };
+
+type StartStage = "convex_connect" | "app_server_init";
+
+class HelperStartError extends Error {
+  readonly stage: StartStage;
+
+  constructor(stage: StartStage, cause: unknown) {
+    super(cause instanceof Error ? cause.message : String(cause));
+    this.name = "HelperStartError";
+    this.stage = stage;
+  }
+}
 
 function isIgnorableProtocolNoise(message: string): boolean {
   return (
@@ -87,6 +99,10 @@ function emit(event: HelperEvent): void {
   process.stdout.write(`${JSON.stringify(event)}\n`);
 }
 
+function emitStartStage(stage: StartStage): void {
+  emit({ type: "global", payload: { kind: "bridge/start_stage", stage } });
+}
+
 function formatWiringValidationFailure(result: unknown): string {
   if (typeof result !== "object" || result === null) {
     return "Host wiring validation failed: unexpected result shape.";
@@ -195,6 +211,9 @@ const DYNAMIC_TOOL_NAMES = new Set<string>(KNOWN_DYNAMIC_TOOLS);
 
 const inFlightDynamicToolCalls = new Set<string>();
 
+const fileChangePathsByItemId = new Map<string, string[]>();
+const fileChangeItemIdByRequestKey = new Map<string, string>();
+
 function normalizeDisabledTools(tools: string[]): string[] {
   return [...new Set(tools.map((tool) => tool.trim()).filter((tool) => tool.length > 0))].sort();
 }
@@ -239,10 +258,78 @@ function asRecord(value: unknown): Record<string, unknown> | null {
   return typeof value === "object" && value !== null ? (value as Record<string, unknown>) : null;
 }
 
-function dynamicToolCallKey(requestId: string | number): string {
+function requestIdKey(requestId: string | number): string {
   return `${typeof requestId}:${String(requestId)}`;
 }
 
+function trackFileChangeItem(payloadJson: string): void {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(payloadJson);
+  } catch {
+    return;
+  }
+  const params = asRecord(asRecord(parsed)?.params);
+  const item = asRecord(params?.item);
+  if (!item || item.type !== "fileChange" || typeof item.id !== "string" || !Array.isArray(item.changes)) {
+    return;
+  }
+  const paths = item.changes
+    .map((change) => asRecord(change)?.path)
+    .filter((path): path is string => typeof path === "string");
+  fileChangePathsByItemId.set(item.id, paths);
+}
+
+function fileChangeItemId(payloadJson: string): string | null {
+  try {
+    const item = asRecord(asRecord(asRecord(JSON.parse(payloadJson))?.params)?.item);
+    return item?.type === "fileChange" && typeof item.id === "string" ? item.id : null;
+  } catch {
+    return null;
+  }
+}
+
+function forgetFileChangeItem(itemId: string): void {
+  fileChangePathsByItemId.delete(itemId);
+  for (const [requestKey, trackedItemId] of fileChangeItemIdByRequestKey) {
+    if (trackedItemId === itemId) {
+      fileChangeItemIdByRequestKey.delete(requestKey);
+    }
+  }
+}
+
+function parseApprovalRequest(kind: string, payloadJson: string): Record<string, unknown> | null {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(payloadJson);
+  } catch {
+    return null;
+  }
+  const root = asRecord(parsed);
+  const params = asRecord(root?.params);
+  const requestId = root?.id;
+  if (!params || (typeof requestId !== "string" && typeof requestId !== "number")) {
+    return null;
+  }
+  const itemId = typeof params.itemId === "string" ? params.itemId : null;
+  if (itemId && kind === "item/fileChange/requestApproval") {
+    fileChangeItemIdByRequestKey.set(requestIdKey(requestId), itemId);
+  }
+  return {
+    kind: "approval/requested",
+    method: kind,
+    requestId,
+    threadId: params.threadId ?? null,
+    turnId: params.turnId ?? null,
+    itemId,
+    command: typeof params.command === "string" ? params.command : null,
+    cwd: typeof params.cwd === "string" ? params.cwd : null,
+    paths: itemId ? fileChangePathsByItemId.get(itemId) ?? [] : [],
+    grantRoot: typeof params.grantRoot === "string" ? params.grantRoot : null,
+    reason: typeof params.reason === "string" ? params.reason : null,
+  };
+}
+
 function parseDynamicToolCallRequest(payloadJson: string): {
   requestId: string | number;
   threadId: string;
@@ -464,7 +551,7 @@ async function handlePendingDynamicToolCalls(threadId: string): Promise<void> {
         continue;
       }
 
-      const key = dynamicToolCallKey(request.requestId);
+      const key = requestIdKey(request.requestId);
       if (inFlightDynamicToolCalls.has(key)) {
         continue;
       }
@@ -588,18 +675,24 @@ async function startBridge(payload: StartPayload): Promise<void> {
     return;
   }
 
+  emitStartStage("convex_connect");
   convex = new ConvexHttpClient(payload.convexUrl);
   actor = payload.actor;
   activeSessionId = payload.sessionId ? `${payload.sessionId}-${randomSessionId()}` : randomSessionId();
   runtimeThreadId = null;
 
-  const wiringValidation = await convex.query(
-    requireDefined(chatApi.validateHostWiring, "api.chat.validateHostWiring"),
-    { actor },
-  );
+  let wiringValidation: unknown;
+  try {
+    wiringValidation = await convex.query(
+      requireDefined(chatApi.validateHostWiring, "api.chat.validateHostWiring"),
+      { actor },
+    );
+  } catch (error) {
+    throw new HelperStartError("convex_connect", error);
+  }
   const wiringFailure = formatWiringValidationFailure(wiringValidation);
   if (wiringFailure) {
-    throw new Error(wiringFailure);
+    throw new HelperStartError("convex_connect", wiringFailure);
   }
 
   startRuntimeOptions = {
@@ -609,6 +702,7 @@ async function startBridge(payload: StartPayload): Promise<void> {
     finishedStreamDeleteDelayMs: 300000,
   };
 
+  emitStartStage("app_server_init");
   runtime = createCodexHostRuntime({
     bridge: {
       cwd: payload.cwd ?? process.cwd(),
@@ -657,6 +751,22 @@ async function startBridge(payload: StartPayload): Promise<void> {
         if (event.kind === "item/tool/call") {
           void handlePendingDynamicToolCalls(event.threadId);
         }
+        if (event.kind === "item/started") {
+          trackFileChangeItem(event.payloadJson);
+        }
+        // An item that completes without a response (interrupted turn) drops its paths too.
+        if (event.kind === "item/completed") {
+          const itemId = fileChangeItemId(event.payloadJson);
+          if (itemId) {
+            forgetFileChangeItem(itemId);
+          }
+        }
+        if (event.kind === "item/commandExecution/requestApproval" || event.kind === "item/fileChange/requestApproval") {
+          const approvalRequest = parseApprovalRequest(event.kind, event.payloadJson);
+          if (approvalRequest) {
+            emit({ type: "global", payload: approvalRequest });
+          }
+        }
         emit({
           type: "event",
           payload: {
@@ -744,7 +854,7 @@ async function startBridge(payload: StartPayload): Promise<void> {
     const message = error instanceof Error ? error.message : String(error);
     emitState({ running: false, phase: "error", source: "runtime", lastErrorCode: null, lastError: message });
     runtime = null;
-    throw error;
+    throw new HelperStartError("app_server_init", error);
   }
 }
 
@@ -893,6 +1003,10 @@ async function respondFileChangeApproval(requestId: string | number, decision: F
     throw new Error("Bridge/runtime not ready. Start runtime first.");
   }
   await runtime.respondFileChangeApproval({ requestId, decision });
+  const itemId = fileChangeItemIdByRequestKey.get(requestIdKey(requestId));
+  if (itemId) {
+    forgetFileChangeItem(itemId);
+  }
 }
 
 async function respondToolUserInput(
@@ -965,7 +1079,9 @@ async function respondChatgptAuthTokensRefresh(args: {
 async function stopCurrentBridge(): Promise<void> {
   try {
     if (runtime) {
+      emit({ type: "global", payload: { kind: "bridge/stop_progress", phase: "ingest_drain", status: "started" } });
       await runtime.stop();
+      emit({ type: "global", payload: { kind: "bridge/stop_progress", phase: "ingest_drain", status: "completed" } });
     }
   } finally {
     runtime = null;
@@ -973,6 +1089,8 @@ async function stopCurrentBridge(): Promise<void> {
     actor = null;
     activeSessionId = null;
     runtimeThreadId = null;
+    fileChangePathsByItemId.clear();
+    fileChangeItemIdByRequestKey.clear();
     emitState({
       running: false,
       phase: "stopped",
@@ -1064,7 +1182,14 @@ function enqueueCommand(command: HelperCommand): void {
     try {
       await handle(command);
     } catch (error) {
-      emit({ type: "error", payload: { message: error instanceof Error ? error.message : String(error) } });
+      emit({
+        type: "error",
+        payload: {
+          message: error instanceof Error ? error.message : String(error),
+          command: command.type,
+          ...(error instanceof HelperStartError ? { stage: error.stage } : {}),
+        },
+      });
     }
   };
   commandQueue = commandQueue.then(run, run);
@@ -1,21 +1,85 @@
+use sha2::{Digest, Sha256};
+
+#[allow(dead_code)]
+#[path = "src/bridge_contract_generated.rs"]
+mod bridge_contract_generated;
+
+#[path = "src/host_commands.rs"]
+mod host_commands;
+
+#[path = "build/protocol_types.rs"]
+mod protocol_types;
+
+// Helper artifacts that may be bundled next to the app; each one found is pinned
+// by file name so the host can check what it is about to spawn.
+const PINNED_HELPERS: &[&str] = &["bridge-helper.js", "bridge-helper", "bridge-helper.exe"];
+const HELPER_DIR: &str = "../dist-node";
+
+fn pin_helper_digests() {
+  // Cargo watches every file under a directory, so rebuilt helpers re-pin.
+  println!("cargo:rerun-if-changed={HELPER_DIR}");
+  let pins = PINNED_HELPERS
+    .iter()
+    .filter_map(|name| {
+      let bytes = std::fs::read(format!("{HELPER_DIR}/{name}")).ok()?;
+      Some(format!("{name}={}", hex::encode(Sha256::digest(&bytes))))
+    })
+    .collect::<Vec<_>>();
+  // Without a pin a release host refuses every helper, so only debug builds
+  // (which skip the check) may go ahead without one.
+  if pins.is_empty() {
+    let message = format!("no helper found in {HELPER_DIR}, so no helper digest was pinned; run `pnpm run build:node` first");
+    if std::env::var("PROFILE").as_deref() == Ok("release") {
+      panic!("{message}");
+    }
+    println!("cargo:warning={message}");
+  }
+  println!("cargo:rustc-env=CODEX_HELPER_PINS={}", pins.join(","));
+}
+
+// The helper's `engines.node` range; the host refuses older Node runtimes.
+fn declare_node_requirement() {
+  println!("cargo:rerun-if-changed=../package.json");
+  let range = std::fs::read_to_string("../package.json")
+    .ok()
+    .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+    .and_then(|manifest| manifest.pointer("/engines/node")?.as_str().map(str::to_string))
+    .unwrap_or_default();
+  println!("cargo:rustc-env=CODEX_NODE_VERSION_REQ={range}");
+}
+
+// Serde types for the protocol and host payloads the typed bridge commands take, written
+// to OUT_DIR and included by src/protocol_types.rs.
+fn generate_protocol_types() {
+  println!("cargo:rerun-if-changed=build/protocol_types.rs");
+  let mut generator = protocol_types::Generator::default();
+  let protocol = protocol_types::SCHEMAS.iter().map(|schema| (protocol_types::SCHEMA_DIR, schema));
+  let host = protocol_types::HOST_SCHEMAS.iter().map(|schema| (protocol_types::HOST_SCHEMA_DIR, schema));
+  for (dir, (file, name)) in protocol.chain(host) {
+    let path = format!("{dir}/{file}.json");
+    println!("cargo:rerun-if-changed={path}");
+    let raw = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("failed to read protocol schema {path}: {e}"));
+    let schema = serde_json::from_str(&raw).unwrap_or_else(|e| panic!("protocol schema {path} is not valid JSON: {e}"));
+    generator.add_schema(file, name, &schema);
+  }
+  let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is set for build scripts");
+  std::fs::write(format!("{out_dir}/protocol_types.rs"), generator.source()).expect("failed to write protocol types");
+}
+
 fn main() {
-  let app_manifest = tauri_build::AppManifest::new().commands(&[
-    "start_bridge",
-    "send_user_turn",
-    "interrupt_turn",
-    "respond_command_approval",
-    "respond_file_change_approval",
-    "respond_tool_user_input",
-    "read_account",
-    "login_account",
-    "cancel_account_login",
-    "logout_account",
-    "read_account_rate_limits",
-    "respond_chatgpt_auth_tokens_refresh",
-    "stop_bridge",
-    "get_bridge_state",
-    "set_disabled_tools",
-  ]);
+  pin_helper_digests();
+  declare_node_requirement();
+  generate_protocol_types();
+  println!("cargo:rerun-if-changed=src/bridge_contract_generated.rs");
+  println!("cargo:rerun-if-changed=src/host_commands.rs");
+
+  // Bridge commands come from the generated contract; host commands from src/host_commands.rs.
+  let commands: Vec<&'static str> = bridge_contract_generated::BRIDGE_COMMANDS
+    .iter()
+    .chain(host_commands::HOST_COMMANDS)
+    .copied()
+    .collect();
+  let app_manifest = tauri_build::AppManifest::new().commands(commands.leak());
 
   tauri_build::try_build(tauri_build::Attributes::new().app_manifest(app_manifest))
     .expect("error while running tauri build script");
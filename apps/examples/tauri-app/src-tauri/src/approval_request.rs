@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+// Approval requests as the helper forwards them. The approval policy matches
+// them and the audit log summarizes them, so they exist without either feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApprovalKind {
+    CommandExecution,
+    FileChange,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(not(feature = "approval-policy"), allow(dead_code))]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequest {
+    pub method: String,
+    pub request_id: serde_json::Value,
+    pub thread_id: Option<String>,
+    pub turn_id: Option<String>,
+    pub item_id: Option<String>,
+    pub command: Option<String>,
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub paths: Vec<String>,
+    pub grant_root: Option<String>,
+}
+
+#[cfg_attr(not(feature = "approval-policy"), allow(dead_code))]
+impl ApprovalRequest {
+    pub fn kind(&self) -> Option<ApprovalKind> {
+        match self.method.as_str() {
+            "item/commandExecution/requestApproval" => Some(ApprovalKind::CommandExecution),
+            "item/fileChange/requestApproval" => Some(ApprovalKind::FileChange),
+            _ => None,
+        }
+    }
+
+    pub fn helper_command(&self) -> Option<&'static str> {
+        match self.kind()? {
+            ApprovalKind::CommandExecution => Some("respond_command_approval"),
+            ApprovalKind::FileChange => Some("respond_file_change_approval"),
+        }
+    }
+}
@@ -0,0 +1,558 @@
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::audit_verification::{AuditVerification, AuditVerificationFailure};
+use crate::env_config;
+
+const AUDIT_DIR_NAME: &str = "audit";
+const CURRENT_FILE_NAME: &str = "audit-log.jsonl";
+const ROTATED_FILE_PREFIX: &str = "audit-log-";
+const DEFAULT_MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+// The key and the head anchor live in the app config dir, outside the log dir.
+// That catches edits, truncation and deletions made to the log dir alone (a
+// sync or backup restore, a cleanup script, a careless edit). It does not stop
+// a local user who can also read the config dir: with the key they can re-sign
+// a rewritten chain and anchor.
+const KEY_FILE_NAME: &str = "audit-log.key";
+const HEAD_FILE_NAME: &str = "audit-log.head.json";
+const KEY_BYTES: usize = 32;
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    pub command: String,
+    pub actor_user_id: Option<String>,
+    pub session_id: Option<String>,
+    pub request_id: serde_json::Value,
+    pub method: Option<String>,
+    pub summary: String,
+    pub decision: serde_json::Value,
+    pub automated: bool,
+    pub rule_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditEntry {
+    seq: u64,
+    ts_ms: u64,
+    #[serde(flatten)]
+    record: AuditRecord,
+    prev_hash: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ChainHead {
+    seq: u64,
+    hash: String,
+}
+
+impl ChainHead {
+    fn follows_into(&self, seq: u64, prev_hash: &str) -> bool {
+        seq == self.seq + 1 && prev_hash == self.hash
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HeadAnchor {
+    seq: u64,
+    hash: String,
+    mac: String,
+}
+
+pub struct AuditLog {
+    dir: PathBuf,
+    head_path: PathBuf,
+    key: Vec<u8>,
+    max_file_bytes: u64,
+    head: Option<ChainHead>,
+}
+
+// One log for the whole app, opened on first use. Sessions share it so its hash
+// chain stays linear.
+#[derive(Default)]
+pub struct SharedAuditLog {
+    log: tokio::sync::Mutex<Option<AuditLog>>,
+}
+
+impl SharedAuditLog {
+    pub async fn append(&self, app: &AppHandle, record: AuditRecord) -> Result<(), String> {
+        let mut log = self.log.lock().await;
+        if log.is_none() {
+            *log = Some(AuditLog::for_app(app)?);
+        }
+        log.as_mut().expect("audit log opened").append(record)
+    }
+
+    pub async fn verify(&self, app: &AppHandle) -> Result<AuditVerification, String> {
+        let mut log = self.log.lock().await;
+        if log.is_none() {
+            *log = Some(AuditLog::for_app(app)?);
+        }
+        log.as_ref().expect("audit log opened").verify()
+    }
+}
+
+fn keyed_digest(key: &[u8], parts: &[&[u8]]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    hex::encode(mac.finalize().into_bytes())
+}
+
+impl AuditEntry {
+    fn compute_hash(&self, key: &[u8]) -> Result<String, String> {
+        let mut unhashed = self.clone();
+        unhashed.hash = String::new();
+        let canonical =
+            serde_json::to_string(&unhashed).map_err(|e| format!("failed to encode audit entry: {e}"))?;
+        Ok(keyed_digest(key, &[self.prev_hash.as_bytes(), b"\n", canonical.as_bytes()]))
+    }
+}
+
+impl HeadAnchor {
+    fn sign(key: &[u8], head: &ChainHead) -> Self {
+        Self {
+            seq: head.seq,
+            hash: head.hash.clone(),
+            mac: keyed_digest(key, &[b"head\n", head.seq.to_string().as_bytes(), b"\n", head.hash.as_bytes()]),
+        }
+    }
+}
+
+impl AuditLog {
+    pub fn for_app(app: &AppHandle) -> Result<Self, String> {
+        let data_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("failed to resolve app data dir for audit log: {e}"))?;
+        let config_dir = app
+            .path()
+            .app_config_dir()
+            .map_err(|e| format!("failed to resolve app config dir for audit log key: {e}"))?;
+        let max_file_bytes = env_config::number("CODEX_AUDIT_LOG_MAX_BYTES")?.unwrap_or(DEFAULT_MAX_FILE_BYTES);
+        let key = load_or_create_key(&config_dir.join(KEY_FILE_NAME))?;
+        Ok(Self::new(
+            data_dir.join(AUDIT_DIR_NAME),
+            config_dir.join(HEAD_FILE_NAME),
+            key,
+            max_file_bytes,
+        ))
+    }
+
+    fn new(dir: PathBuf, head_path: PathBuf, key: Vec<u8>, max_file_bytes: u64) -> Self {
+        Self {
+            dir,
+            head_path,
+            key,
+            max_file_bytes,
+            head: None,
+        }
+    }
+
+    pub fn append(&mut self, record: AuditRecord) -> Result<(), String> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("failed to create audit log dir {}: {e}", self.dir.display()))?;
+        if self.head.is_none() {
+            self.head = Some(self.read_head()?);
+        }
+        self.rotate_if_needed()?;
+
+        let head = self.head.as_ref().expect("audit chain head loaded");
+        let mut entry = AuditEntry {
+            seq: head.seq + 1,
+            ts_ms: crate::now_unix_ms() as u64,
+            record,
+            prev_hash: head.hash.clone(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash(&self.key)?;
+        let line = serde_json::to_string(&entry).map_err(|e| format!("failed to encode audit entry: {e}"))?;
+
+        let path = self.current_path();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("failed to open audit log {}: {e}", path.display()))?;
+        file.write_all(format!("{line}\n").as_bytes())
+            .and_then(|_| file.sync_data())
+            .map_err(|e| format!("failed to append audit log {}: {e}", path.display()))?;
+
+        let head = ChainHead {
+            seq: entry.seq,
+            hash: entry.hash,
+        };
+        self.write_anchor(&head)?;
+        self.head = Some(head);
+        Ok(())
+    }
+
+    pub fn verify(&self) -> Result<AuditVerification, String> {
+        let files = self.chain_files()?;
+        let mut verification = AuditVerification {
+            ok: true,
+            directory: self.dir.to_string_lossy().to_string(),
+            files: files
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect(),
+            entries: 0,
+            head_hash: GENESIS_HASH.to_string(),
+            failure: None,
+        };
+
+        let mut expected_seq = 1;
+        let mut tail_prev_hash = GENESIS_HASH.to_string();
+        for path in &files {
+            let file = fs::File::open(path).map_err(|e| format!("failed to open {}: {e}", path.display()))?;
+            for (index, line) in BufReader::new(file).lines().enumerate() {
+                let line = line.map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let entry = match verify_line(&line, expected_seq, &verification.head_hash, &self.key) {
+                    Ok(entry) => entry,
+                    Err(reason) => {
+                        verification.ok = false;
+                        verification.failure = Some(AuditVerificationFailure {
+                            file: path.to_string_lossy().to_string(),
+                            line: index + 1,
+                            reason,
+                        });
+                        return Ok(verification);
+                    }
+                };
+                verification.entries += 1;
+                tail_prev_hash = std::mem::replace(&mut verification.head_hash, entry.hash);
+                expected_seq += 1;
+            }
+        }
+
+        // A chain that verifies can still have lost its newest entries or
+        // whole files; only the anchor records where it should end.
+        let tail = ChainHead {
+            seq: verification.entries,
+            hash: verification.head_hash.clone(),
+        };
+        let reason = match self.read_anchor() {
+            Err(reason) => Some(reason),
+            Ok(None) if tail.seq > 0 => Some(format!(
+                "head anchor {} is missing, so truncation cannot be ruled out",
+                self.head_path.display()
+            )),
+            Ok(None) => None,
+            Ok(Some(anchor)) if anchor.follows_into(tail.seq, &tail_prev_hash) => None,
+            Ok(Some(anchor)) if anchor != tail => Some(format!(
+                "the log ends at seq {} but the head anchor records seq {}; entries were removed or replaced",
+                tail.seq, anchor.seq
+            )),
+            Ok(Some(_)) => None,
+        };
+        if let Some(reason) = reason {
+            verification.ok = false;
+            verification.failure = Some(AuditVerificationFailure {
+                file: self.current_path().to_string_lossy().to_string(),
+                line: 0,
+                reason,
+            });
+        }
+        Ok(verification)
+    }
+
+    fn current_path(&self) -> PathBuf {
+        self.dir.join(CURRENT_FILE_NAME)
+    }
+
+    fn rotate_if_needed(&mut self) -> Result<(), String> {
+        let current = self.current_path();
+        let size = fs::metadata(&current).map(|meta| meta.len()).unwrap_or(0);
+        if size == 0 || size < self.max_file_bytes {
+            return Ok(());
+        }
+        let last_seq = self.head.as_ref().map(|head| head.seq).unwrap_or(0);
+        let rotated = self.dir.join(format!("{ROTATED_FILE_PREFIX}{last_seq:012}.jsonl"));
+        fs::rename(&current, &rotated).map_err(|e| {
+            format!(
+                "failed to rotate audit log {} -> {}: {e}",
+                current.display(),
+                rotated.display()
+            )
+        })
+    }
+
+    // Rotated files carry the last sequence number in their zero-padded name,
+    // so lexical order is chain order; the live file always comes last.
+    fn chain_files(&self) -> Result<Vec<PathBuf>, String> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut rotated: Vec<PathBuf> = fs::read_dir(&self.dir)
+            .map_err(|e| format!("failed to list audit log dir {}: {e}", self.dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy())
+                    .is_some_and(|name| name.starts_with(ROTATED_FILE_PREFIX) && name.ends_with(".jsonl"))
+            })
+            .collect();
+        rotated.sort();
+        let current = self.current_path();
+        if current.exists() {
+            rotated.push(current);
+        }
+        Ok(rotated)
+    }
+
+    // The anchor wins over the file tail, so entries appended after a
+    // truncation do not link up and the gap stays visible to verify(). The one
+    // exception is a signed entry exactly one past the anchor: append() syncs
+    // the entry before the anchor, so that is what a crash between the two
+    // leaves behind.
+    fn read_head(&self) -> Result<ChainHead, String> {
+        let Some(anchor) = self.read_anchor()? else {
+            return Ok(self.read_tail()?.map_or_else(
+                || ChainHead {
+                    seq: 0,
+                    hash: GENESIS_HASH.to_string(),
+                },
+                |entry| ChainHead {
+                    seq: entry.seq,
+                    hash: entry.hash,
+                },
+            ));
+        };
+        if let Ok(Some(entry)) = self.read_tail() {
+            if anchor.follows_into(entry.seq, &entry.prev_hash) && entry.compute_hash(&self.key)? == entry.hash {
+                return Ok(ChainHead {
+                    seq: entry.seq,
+                    hash: entry.hash,
+                });
+            }
+        }
+        Ok(anchor)
+    }
+
+    fn read_tail(&self) -> Result<Option<AuditEntry>, String> {
+        for path in self.chain_files()?.iter().rev() {
+            if let Some(line) = last_non_empty_line(path)? {
+                return serde_json::from_str(&line)
+                    .map(Some)
+                    .map_err(|e| format!("audit log {} has a corrupt tail entry: {e}", path.display()));
+            }
+        }
+        Ok(None)
+    }
+
+    fn read_anchor(&self) -> Result<Option<ChainHead>, String> {
+        let raw = match fs::read_to_string(&self.head_path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("failed to read audit head anchor {}: {e}", self.head_path.display())),
+        };
+        let anchor: HeadAnchor = serde_json::from_str(&raw)
+            .map_err(|e| format!("audit head anchor {} is not valid JSON: {e}", self.head_path.display()))?;
+        let head = ChainHead {
+            seq: anchor.seq,
+            hash: anchor.hash,
+        };
+        if HeadAnchor::sign(&self.key, &head).mac != anchor.mac {
+            return Err(format!("audit head anchor {} signature does not match", self.head_path.display()));
+        }
+        Ok(Some(head))
+    }
+
+    fn write_anchor(&self, head: &ChainHead) -> Result<(), String> {
+        if let Some(parent) = self.head_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+        }
+        let encoded = serde_json::to_vec(&HeadAnchor::sign(&self.key, head))
+            .map_err(|e| format!("failed to encode audit head anchor: {e}"))?;
+        let staged = self.head_path.with_extension("json.tmp");
+        fs::write(&staged, encoded)
+            .and_then(|_| fs::rename(&staged, &self.head_path))
+            .map_err(|e| format!("failed to write audit head anchor {}: {e}", self.head_path.display()))
+    }
+}
+
+fn load_or_create_key(path: &Path) -> Result<Vec<u8>, String> {
+    match fs::read_to_string(path) {
+        Ok(raw) => {
+            let key = hex::decode(raw.trim())
+                .map_err(|e| format!("audit log key {} is not hex: {e}", path.display()))?;
+            if key.len() != KEY_BYTES {
+                return Err(format!("audit log key {} must be {KEY_BYTES} bytes", path.display()));
+            }
+            Ok(key)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let mut key = vec![0u8; KEY_BYTES];
+            getrandom::getrandom(&mut key).map_err(|e| format!("failed to generate audit log key: {e}"))?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+            }
+            let mut options = OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            options
+                .open(path)
+                .and_then(|mut file| file.write_all(hex::encode(&key).as_bytes()))
+                .map_err(|e| format!("failed to write audit log key {}: {e}", path.display()))?;
+            Ok(key)
+        }
+        Err(e) => Err(format!("failed to read audit log key {}: {e}", path.display())),
+    }
+}
+
+fn verify_line(line: &str, expected_seq: u64, expected_prev_hash: &str, key: &[u8]) -> Result<AuditEntry, String> {
+    let entry: AuditEntry = serde_json::from_str(line).map_err(|e| format!("entry is not valid JSON: {e}"))?;
+    if entry.seq != expected_seq {
+        return Err(format!("expected seq {expected_seq}, found {}", entry.seq));
+    }
+    if entry.prev_hash != expected_prev_hash {
+        return Err(format!("seq {} does not link to the previous entry hash", entry.seq));
+    }
+    if entry.compute_hash(key)? != entry.hash {
+        return Err(format!("seq {} hash does not match its contents", entry.seq));
+    }
+    Ok(entry)
+}
+
+fn last_non_empty_line(path: &Path) -> Result<Option<String>, String> {
+    let file = fs::File::open(path).map_err(|e| format!("failed to open {}: {e}", path.display()))?;
+    let mut last = None;
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        if !line.trim().is_empty() {
+            last = Some(line);
+        }
+    }
+    Ok(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codex-audit-log-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn open(root: &Path, max_file_bytes: u64) -> AuditLog {
+        AuditLog::new(root.join("data"), root.join("config").join(HEAD_FILE_NAME), vec![7; KEY_BYTES], max_file_bytes)
+    }
+
+    fn record(summary: &str) -> AuditRecord {
+        AuditRecord {
+            command: "respond_command_approval".to_string(),
+            actor_user_id: None,
+            session_id: Some("default".to_string()),
+            request_id: serde_json::json!(1),
+            method: None,
+            summary: summary.to_string(),
+            decision: serde_json::json!("accept"),
+            automated: false,
+            rule_id: None,
+        }
+    }
+
+    fn append_all(log: &mut AuditLog, count: usize) {
+        for index in 0..count {
+            log.append(record(&format!("cargo test {index}"))).unwrap();
+        }
+    }
+
+    fn failure(log: &AuditLog) -> String {
+        let verification = log.verify().unwrap();
+        assert!(!verification.ok);
+        verification.failure.unwrap().reason
+    }
+
+    #[test]
+    fn appended_entries_verify_across_rotation_and_reopen() {
+        let root = scratch("rotate");
+        let mut log = open(&root, 1);
+        append_all(&mut log, 3);
+        let mut reopened = open(&root, 1);
+        append_all(&mut reopened, 1);
+
+        let verification = reopened.verify().unwrap();
+        assert!(verification.ok, "{:?}", verification.failure);
+        assert_eq!(verification.entries, 4);
+        assert_eq!(verification.files.len(), 4);
+    }
+
+    #[test]
+    fn an_edited_line_fails_verification() {
+        let root = scratch("tamper");
+        let mut log = open(&root, DEFAULT_MAX_FILE_BYTES);
+        append_all(&mut log, 3);
+        let current = log.current_path();
+        let tampered = fs::read_to_string(&current).unwrap().replace("cargo test 1", "rm -rf /");
+        fs::write(&current, tampered).unwrap();
+
+        assert_eq!(failure(&log), "seq 2 hash does not match its contents");
+    }
+
+    #[test]
+    fn a_chain_rehashed_without_the_key_fails_verification() {
+        let root = scratch("rekey");
+        let mut log = open(&root, DEFAULT_MAX_FILE_BYTES);
+        append_all(&mut log, 2);
+        fs::remove_file(log.current_path()).unwrap();
+        let mut forger = AuditLog::new(log.dir.clone(), root.join("forged.json"), vec![9; KEY_BYTES], DEFAULT_MAX_FILE_BYTES);
+        append_all(&mut forger, 2);
+
+        assert_eq!(failure(&log), "seq 1 hash does not match its contents");
+    }
+
+    #[test]
+    fn truncation_and_deletion_fail_verification() {
+        let root = scratch("truncate");
+        let mut log = open(&root, DEFAULT_MAX_FILE_BYTES);
+        append_all(&mut log, 3);
+        let current = log.current_path();
+        let kept: Vec<String> = fs::read_to_string(&current).unwrap().lines().take(2).map(str::to_string).collect();
+        fs::write(&current, format!("{}\n", kept.join("\n"))).unwrap();
+        assert!(failure(&log).starts_with("the log ends at seq 2 but the head anchor records seq 3"));
+
+        fs::remove_file(&current).unwrap();
+        assert!(failure(&log).starts_with("the log ends at seq 0 but the head anchor records seq 3"));
+    }
+
+    #[test]
+    fn an_entry_synced_before_its_anchor_still_verifies() {
+        let root = scratch("crash");
+        let mut log = open(&root, DEFAULT_MAX_FILE_BYTES);
+        append_all(&mut log, 2);
+        let anchor = fs::read(&log.head_path).unwrap();
+        append_all(&mut log, 1);
+        fs::write(&log.head_path, &anchor).unwrap();
+
+        let verification = log.verify().unwrap();
+        assert!(verification.ok, "{:?}", verification.failure);
+        assert_eq!(verification.entries, 3);
+
+        let mut reopened = open(&root, DEFAULT_MAX_FILE_BYTES);
+        append_all(&mut reopened, 1);
+        let verification = reopened.verify().unwrap();
+        assert!(verification.ok, "{:?}", verification.failure);
+        assert_eq!(verification.entries, 4);
+
+        fs::write(&log.head_path, &anchor).unwrap();
+        assert!(failure(&log).starts_with("the log ends at seq 4 but the head anchor records seq 2"));
+    }
+}
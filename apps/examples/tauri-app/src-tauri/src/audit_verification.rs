@@ -0,0 +1,41 @@
+use serde::Serialize;
+use tauri::AppHandle;
+#[cfg(feature = "audit-log")]
+use tauri::Manager;
+
+#[cfg(feature = "audit-log")]
+use crate::audit_log::SharedAuditLog;
+
+// Result of `verify_audit_log`. It stays in every build so the command keeps
+// its signature when the audit log is compiled out.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct AuditVerification {
+    pub ok: bool,
+    pub directory: String,
+    pub files: Vec<String>,
+    #[cfg_attr(test, ts(as = "f64"))]
+    pub entries: u64,
+    pub head_hash: String,
+    pub failure: Option<AuditVerificationFailure>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct AuditVerificationFailure {
+    pub file: String,
+    pub line: usize,
+    pub reason: String,
+}
+
+#[cfg(feature = "audit-log")]
+pub async fn verify(app: &AppHandle) -> Result<AuditVerification, String> {
+    app.state::<SharedAuditLog>().verify(app).await
+}
+
+#[cfg(not(feature = "audit-log"))]
+pub async fn verify(_app: &AppHandle) -> Result<AuditVerification, String> {
+    Err(crate::feature_disabled("audit-log", "the approval audit log"))
+}
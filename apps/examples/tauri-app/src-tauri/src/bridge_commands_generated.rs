@@ -0,0 +1,170 @@
+// AUTO-GENERATED FILE. DO NOT EDIT.
+// Source: @zakstam/codex-local-component/host/tauri
+
+#[tauri::command]
+async fn refresh_local_threads(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+) -> Result<(), String> {
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "refresh_local_threads", serde_json::json!({}))
+        .await
+}
+
+#[tauri::command]
+async fn send_user_turn(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+    text: String,
+) -> Result<(), String> {
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "send_user_turn", serde_json::json!({ "text": text }))
+        .await
+}
+
+#[tauri::command]
+async fn interrupt_turn(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+) -> Result<(), String> {
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "interrupt_turn", serde_json::json!({}))
+        .await
+}
+
+#[tauri::command]
+async fn respond_command_approval(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+    config: Validated<RespondCommandApprovalConfig>,
+) -> Result<(), String> {
+    let payload = serde_json::to_value(config)
+        .map_err(|e| format!("failed to encode respond_command_approval payload: {e}"))?;
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "respond_command_approval", payload)
+        .await
+}
+
+#[tauri::command]
+async fn respond_file_change_approval(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+    config: Validated<RespondFileChangeApprovalConfig>,
+) -> Result<(), String> {
+    let payload = serde_json::to_value(config)
+        .map_err(|e| format!("failed to encode respond_file_change_approval payload: {e}"))?;
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "respond_file_change_approval", payload)
+        .await
+}
+
+#[tauri::command]
+async fn respond_tool_user_input(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+    config: Validated<RespondToolUserInputConfig>,
+) -> Result<(), String> {
+    let payload = serde_json::to_value(config)
+        .map_err(|e| format!("failed to encode respond_tool_user_input payload: {e}"))?;
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "respond_tool_user_input", payload)
+        .await
+}
+
+#[tauri::command]
+async fn read_account(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+    config: Validated<GetAccountParams>,
+) -> Result<(), String> {
+    let payload = serde_json::to_value(config)
+        .map_err(|e| format!("failed to encode read_account payload: {e}"))?;
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "read_account", payload)
+        .await
+}
+
+#[tauri::command]
+async fn login_account(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+    config: Validated<LoginAccountConfig>,
+) -> Result<(), String> {
+    let payload = serde_json::to_value(config)
+        .map_err(|e| format!("failed to encode login_account payload: {e}"))?;
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "login_account", payload)
+        .await
+}
+
+#[tauri::command]
+async fn cancel_account_login(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+    config: Validated<CancelLoginAccountParams>,
+) -> Result<(), String> {
+    let payload = serde_json::to_value(config)
+        .map_err(|e| format!("failed to encode cancel_account_login payload: {e}"))?;
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "cancel_account_login", payload)
+        .await
+}
+
+#[tauri::command]
+async fn logout_account(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+) -> Result<(), String> {
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "logout_account", serde_json::json!({}))
+        .await
+}
+
+#[tauri::command]
+async fn read_account_rate_limits(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+) -> Result<(), String> {
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "read_account_rate_limits", serde_json::json!({}))
+        .await
+}
+
+#[tauri::command]
+async fn respond_chatgpt_auth_tokens_refresh(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+    config: Validated<RespondChatgptAuthTokensRefreshConfig>,
+) -> Result<(), String> {
+    let payload = serde_json::to_value(config)
+        .map_err(|e| format!("failed to encode respond_chatgpt_auth_tokens_refresh payload: {e}"))?;
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "respond_chatgpt_auth_tokens_refresh", payload)
+        .await
+}
+
+#[tauri::command]
+async fn set_disabled_tools(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BridgeHostState>,
+    session_id: Option<String>,
+    config: Validated<SetDisabledToolsConfig>,
+) -> Result<(), String> {
+    let payload = serde_json::to_value(config)
+        .map_err(|e| format!("failed to encode set_disabled_tools payload: {e}"))?;
+    state
+        .forward_bridge_command(app, session_id.as_deref(), "set_disabled_tools", payload)
+        .await
+}
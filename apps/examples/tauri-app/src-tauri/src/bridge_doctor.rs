@@ -0,0 +1,441 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+use tokio::time::{timeout, Duration};
+
+use crate::helper_integrity::{self, IntegrityStatus};
+use crate::helper_process_policy::HelperProcessPolicy;
+#[cfg(feature = "node-helper")]
+use crate::helper_resolution::CandidateRuntime;
+use crate::helper_resolution::{self, ResolvedHelper};
+use crate::host_config::HostConfig;
+#[cfg(feature = "node-helper")]
+use crate::node_runtime;
+use crate::process_tree;
+use crate::workspace_roots;
+
+const DEFAULT_HANDSHAKE_TIMEOUT_MS: u64 = 10_000;
+const ENDPOINT_TIMEOUT: Duration = Duration::from_secs(3);
+const PROBE_EXIT_GRACE: Duration = Duration::from_secs(1);
+const REPORT_FILE_NAME: &str = "bridge-doctor.json";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS), ts(optional_fields = nullable))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DoctorConfig {
+    pub convex_url: Option<String>,
+    pub cwd: Option<String>,
+    pub local_endpoint: Option<String>,
+    #[cfg_attr(test, ts(as = "Option<f64>"))]
+    pub handshake_timeout_ms: Option<u64>,
+    pub skip_handshake: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorCheck {
+    pub id: &'static str,
+    pub status: CheckStatus,
+    pub summary: String,
+    #[cfg_attr(test, ts(type = "unknown"))]
+    pub detail: Option<serde_json::Value>,
+}
+
+impl DoctorCheck {
+    fn new(id: &'static str, status: CheckStatus, summary: impl Into<String>) -> Self {
+        Self {
+            id,
+            status,
+            summary: summary.into(),
+            detail: None,
+        }
+    }
+
+    fn with_detail(mut self, detail: serde_json::Value) -> Self {
+        self.detail = Some(detail);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorReport {
+    // Worst status across the checks; skipped checks do not count.
+    pub status: CheckStatus,
+    pub checks: Vec<DoctorCheck>,
+    #[cfg_attr(test, ts(as = "f64"))]
+    pub generated_at_ms: u128,
+}
+
+// Runs every check without touching running sessions; the handshake check
+// spawns its own short-lived helper.
+pub async fn run(app: &AppHandle, config: DoctorConfig) -> DoctorReport {
+    let mut checks = Vec::new();
+
+    let (resolution, trace) = match app.try_state::<helper_resolution::HelperResolver>() {
+        Some(resolver) => resolver.resolve(app),
+        None => helper_resolution::HelperResolver::default().resolve(app),
+    };
+    let helper = match resolution {
+        Ok(helper) => {
+            checks.push(
+                DoctorCheck::new(
+                    "helper_resolution",
+                    CheckStatus::Pass,
+                    format!("{} ({})", helper.command.display(), helper.mode),
+                )
+                .with_detail(json!(trace)),
+            );
+            Some(helper)
+        }
+        Err(message) => {
+            checks.push(DoctorCheck::new("helper_resolution", CheckStatus::Fail, message).with_detail(json!(trace)));
+            None
+        }
+    };
+
+    let node_ok = match &helper {
+        Some(helper) => {
+            let check = check_node(helper).await;
+            let ok = check.status != CheckStatus::Fail;
+            checks.push(check);
+            ok
+        }
+        None => {
+            checks.push(DoctorCheck::new("node_runtime", CheckStatus::Skip, "no helper resolved"));
+            false
+        }
+    };
+
+    let integrity = match &helper {
+        Some(helper) => check_integrity(app, helper),
+        None => DoctorCheck::new("helper_integrity", CheckStatus::Skip, "no helper resolved"),
+    };
+    // Only a helper that passed (or was exempt from) verification is spawned.
+    let integrity_ok = matches!(integrity.status, CheckStatus::Pass | CheckStatus::Skip);
+    checks.push(integrity);
+
+    checks.push(match &helper {
+        _ if config.skip_handshake.unwrap_or(false) => {
+            DoctorCheck::new("helper_handshake", CheckStatus::Skip, "skipped by request")
+        }
+        Some(helper) if node_ok && integrity_ok => {
+            let deadline = Duration::from_millis(config.handshake_timeout_ms.unwrap_or(DEFAULT_HANDSHAKE_TIMEOUT_MS));
+            check_handshake(helper, deadline).await
+        }
+        Some(_) if !node_ok => DoctorCheck::new("helper_handshake", CheckStatus::Skip, "node runtime check failed"),
+        Some(_) => DoctorCheck::new("helper_handshake", CheckStatus::Skip, "helper integrity check did not pass"),
+        None => DoctorCheck::new("helper_handshake", CheckStatus::Skip, "no helper resolved"),
+    });
+
+    checks.push(check_app_data_dir(app));
+    checks.push(check_cwd(app, config.cwd.as_deref()));
+
+    let convex_url = config
+        .convex_url
+        .or_else(|| std::env::var("VITE_CONVEX_URL").ok())
+        .or_else(|| std::env::var("CONVEX_URL").ok())
+        .filter(|url| !url.trim().is_empty());
+    let (convex_check, parsed_convex_url) = check_convex_url(convex_url.as_deref());
+    checks.push(convex_check);
+
+    // A Convex URL on loopback points at a local backend, which is worth probing
+    // even when no endpoint was configured explicitly.
+    let endpoint = config.local_endpoint.or_else(|| {
+        parsed_convex_url
+            .filter(|url| url.host().is_some_and(is_loopback_host))
+            .map(|url| url.to_string())
+    });
+    checks.push(check_local_endpoint(endpoint.as_deref()).await);
+
+    let status = if checks.iter().any(|check| check.status == CheckStatus::Fail) {
+        CheckStatus::Fail
+    } else if checks.iter().any(|check| check.status == CheckStatus::Warn) {
+        CheckStatus::Warn
+    } else {
+        CheckStatus::Pass
+    };
+    DoctorReport {
+        status,
+        checks,
+        generated_at_ms: crate::now_unix_ms(),
+    }
+}
+
+// `--doctor` may run without a console (a Windows GUI-subsystem build has none),
+// so the report is always written to the app log dir as well.
+pub fn write_report(app: &AppHandle, encoded: &str) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("failed to resolve app log dir for the doctor report: {e}"))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+    let path = dir.join(REPORT_FILE_NAME);
+    std::fs::write(&path, encoded).map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(feature = "node-helper")]
+async fn check_node(helper: &ResolvedHelper) -> DoctorCheck {
+    if helper.runtime != CandidateRuntime::Node {
+        return DoctorCheck::new("node_runtime", CheckStatus::Skip, "standalone helper does not need Node.js");
+    }
+    let node = helper.command.clone();
+    match tokio::task::spawn_blocking(move || node_runtime::check_node(&node)).await {
+        Ok(Ok(version)) => DoctorCheck::new(
+            "node_runtime",
+            CheckStatus::Pass,
+            format!("Node.js v{version} at {}", helper.command.display()),
+        ),
+        Ok(Err(error)) => DoctorCheck::new("node_runtime", CheckStatus::Fail, error.message)
+            .with_detail(json!({ "code": error.code.as_str() })),
+        Err(error) => DoctorCheck::new("node_runtime", CheckStatus::Fail, format!("node runtime probe failed: {error}")),
+    }
+}
+
+// Only standalone helpers resolve without the `node-helper` feature.
+#[cfg(not(feature = "node-helper"))]
+async fn check_node(_helper: &ResolvedHelper) -> DoctorCheck {
+    DoctorCheck::new("node_runtime", CheckStatus::Skip, "standalone helper does not need Node.js")
+}
+
+fn check_integrity(app: &AppHandle, helper: &ResolvedHelper) -> DoctorCheck {
+    let allow_unverified = app.state::<HostConfig>().allow_unverified_helper;
+    match helper_integrity::verify_helper(&helper_script(helper), allow_unverified) {
+        Ok(IntegrityStatus::NotEnforced) => {
+            DoctorCheck::new("helper_integrity", CheckStatus::Skip, "not enforced in debug builds")
+        }
+        Ok(IntegrityStatus::Verified) => {
+            DoctorCheck::new("helper_integrity", CheckStatus::Pass, "helper matches the pinned digest")
+        }
+        Ok(IntegrityStatus::Unverified) => DoctorCheck::new(
+            "helper_integrity",
+            CheckStatus::Warn,
+            "allowUnverifiedHelper is set in plugins.codexHost; the helper runs unverified",
+        ),
+        Err(message) => DoctorCheck::new("helper_integrity", CheckStatus::Fail, message),
+    }
+}
+
+fn helper_script(helper: &ResolvedHelper) -> PathBuf {
+    helper.args.first().map(PathBuf::from).unwrap_or_else(|| helper.command.clone())
+}
+
+// Spawns a throwaway helper and waits for it to answer `status` with a state
+// line. The helper is never sent `start`, so no Convex or app-server work runs.
+async fn check_handshake(helper: &ResolvedHelper, deadline: Duration) -> DoctorCheck {
+    let policy = match HelperProcessPolicy::resolve(None) {
+        Ok(policy) => policy,
+        Err(message) => return DoctorCheck::new("helper_handshake", CheckStatus::Fail, message),
+    };
+    let mut command = Command::new(&helper.command);
+    command.args(&helper.args);
+    policy.apply(&mut command);
+    process_tree::isolate_process_tree(&mut command);
+    command
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true);
+    let mut child = match process_tree::spawn(command).await {
+        Ok(child) => child,
+        Err(error) => {
+            return DoctorCheck::new("helper_handshake", CheckStatus::Fail, format!("failed to spawn helper: {error}"));
+        }
+    };
+    let (Some(mut stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+        return DoctorCheck::new("helper_handshake", CheckStatus::Fail, "helper stdio unavailable");
+    };
+
+    let started = std::time::Instant::now();
+    let exchange = async {
+        let line = json!({ "type": "status", "payload": {} }).to_string();
+        stdin
+            .write_all(format!("{line}\n").as_bytes())
+            .await
+            .map_err(|e| format!("failed to write to helper stdin: {e}"))?;
+        stdin.flush().await.map_err(|e| format!("failed to flush helper stdin: {e}"))?;
+        let mut lines = BufReader::new(stdout).lines();
+        while let Some(line) = lines
+            .next_line()
+            .await
+            .map_err(|e| format!("failed to read helper stdout: {e}"))?
+        {
+            let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&line) else {
+                return Err(format!("helper wrote a non-JSON line: {}", crate::redaction::redact_text(&line)));
+            };
+            match parsed.get("type").and_then(|value| value.as_str()) {
+                Some("state") => return Ok(()),
+                Some("error") => {
+                    let message = parsed
+                        .pointer("/payload/message")
+                        .and_then(|value| value.as_str())
+                        .unwrap_or("helper reported an error");
+                    return Err(crate::redaction::redact_text(message));
+                }
+                _ => {}
+            }
+        }
+        Err("helper exited before answering".to_string())
+    };
+    let result = timeout(deadline, exchange).await;
+
+    // Closing stdin asks the helper to shut down; kill it if it lingers.
+    drop(stdin);
+    if timeout(PROBE_EXIT_GRACE, child.wait()).await.is_err() {
+        let _ = child.kill().await;
+    }
+
+    let elapsed_ms = started.elapsed().as_millis();
+    match result {
+        Ok(Ok(())) => DoctorCheck::new("helper_handshake", CheckStatus::Pass, format!("helper answered in {elapsed_ms}ms"))
+            .with_detail(json!({ "elapsedMs": elapsed_ms })),
+        Ok(Err(message)) => DoctorCheck::new("helper_handshake", CheckStatus::Fail, message),
+        Err(_) => DoctorCheck::new(
+            "helper_handshake",
+            CheckStatus::Fail,
+            format!("helper did not answer within {}ms", deadline.as_millis()),
+        ),
+    }
+}
+
+fn check_app_data_dir(app: &AppHandle) -> DoctorCheck {
+    let dir = match app.path().app_data_dir() {
+        Ok(dir) => dir,
+        Err(error) => {
+            return DoctorCheck::new("app_data_dir", CheckStatus::Fail, format!("failed to resolve app data dir: {error}"));
+        }
+    };
+    let probe = dir.join(format!(".doctor-probe-{}", std::process::id()));
+    let result = std::fs::create_dir_all(&dir)
+        .and_then(|()| std::fs::write(&probe, b"ok"))
+        .and_then(|()| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => DoctorCheck::new("app_data_dir", CheckStatus::Pass, format!("{} is writable", dir.display())),
+        Err(error) => DoctorCheck::new("app_data_dir", CheckStatus::Fail, format!("{} is not writable: {error}", dir.display())),
+    }
+}
+
+fn check_cwd(app: &AppHandle, requested: Option<&str>) -> DoctorCheck {
+    let cwd = match workspace_roots::resolve_helper_cwd(app, requested) {
+        Ok(cwd) => PathBuf::from(cwd),
+        Err(error) => return DoctorCheck::new("cwd", CheckStatus::Fail, error.to_string()),
+    };
+    match std::fs::read_dir(&cwd) {
+        Ok(_) => DoctorCheck::new("cwd", CheckStatus::Pass, format!("{} is readable", cwd.display())),
+        Err(error) => DoctorCheck::new("cwd", CheckStatus::Fail, format!("{} is not readable: {error}", cwd.display())),
+    }
+}
+
+fn check_convex_url(convex_url: Option<&str>) -> (DoctorCheck, Option<url::Url>) {
+    let Some(raw) = convex_url.map(str::trim) else {
+        let check = DoctorCheck::new(
+            "convex_url",
+            CheckStatus::Warn,
+            "no Convex URL configured (pass convexUrl or set VITE_CONVEX_URL)",
+        );
+        return (check, None);
+    };
+    let parsed = match url::Url::parse(raw) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            return (DoctorCheck::new("convex_url", CheckStatus::Fail, format!("{raw} is not a valid URL: {error}")), None);
+        }
+    };
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host().is_none() {
+        let check = DoctorCheck::new("convex_url", CheckStatus::Fail, format!("{raw} must be an http(s) URL with a host"));
+        return (check, None);
+    }
+    (DoctorCheck::new("convex_url", CheckStatus::Pass, raw.to_string()), Some(parsed))
+}
+
+fn is_loopback_host(host: url::Host<&str>) -> bool {
+    match host {
+        url::Host::Domain(domain) => domain.eq_ignore_ascii_case("localhost"),
+        url::Host::Ipv4(ip) => ip.is_loopback(),
+        url::Host::Ipv6(ip) => ip.is_loopback(),
+    }
+}
+
+// The endpoint can come from the webview, so only loopback addresses are probed;
+// anything else would turn the doctor into a port scanner.
+async fn check_local_endpoint(endpoint: Option<&str>) -> DoctorCheck {
+    let Some(endpoint) = endpoint else {
+        return DoctorCheck::new("local_endpoint", CheckStatus::Skip, "no local endpoint configured");
+    };
+    let addresses = match loopback_addresses(endpoint) {
+        Ok(addresses) => addresses,
+        Err(message) => return DoctorCheck::new("local_endpoint", CheckStatus::Fail, message),
+    };
+    let address = endpoint.trim();
+    match timeout(ENDPOINT_TIMEOUT, tokio::net::TcpStream::connect(addresses.as_slice())).await {
+        Ok(Ok(_)) => DoctorCheck::new("local_endpoint", CheckStatus::Pass, format!("{address} accepts connections")),
+        Ok(Err(error)) => DoctorCheck::new("local_endpoint", CheckStatus::Fail, format!("{address} is unreachable: {error}")),
+        Err(_) => DoctorCheck::new(
+            "local_endpoint",
+            CheckStatus::Fail,
+            format!("{address} did not accept a connection within {}ms", ENDPOINT_TIMEOUT.as_millis()),
+        ),
+    }
+}
+
+// Accepts a URL or a bare `host:port`. `localhost` maps to both loopback addresses
+// instead of going through the resolver, so a hosts-file entry cannot redirect it.
+fn loopback_addresses(endpoint: &str) -> Result<Vec<SocketAddr>, String> {
+    let endpoint = endpoint.trim();
+    let parsed = url::Url::parse(endpoint)
+        .ok()
+        .filter(|url| url.has_host())
+        .or_else(|| url::Url::parse(&format!("http://{endpoint}")).ok())
+        .ok_or_else(|| format!("{endpoint} is not a URL or host:port"))?;
+    let (Some(host), Some(port)) = (parsed.host(), parsed.port_or_known_default()) else {
+        return Err(format!("{endpoint} has no host and port"));
+    };
+    let ips: Vec<IpAddr> = match host {
+        url::Host::Domain(domain) if domain.eq_ignore_ascii_case("localhost") => {
+            vec![Ipv4Addr::LOCALHOST.into(), Ipv6Addr::LOCALHOST.into()]
+        }
+        url::Host::Ipv4(ip) if ip.is_loopback() => vec![ip.into()],
+        url::Host::Ipv6(ip) if ip.is_loopback() => vec![ip.into()],
+        _ => return Err(format!("{endpoint} is not a loopback address; only local endpoints are probed")),
+    };
+    Ok(ips.into_iter().map(|ip| SocketAddr::new(ip, port)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_endpoints_must_be_loopback() {
+        let v4 = |port| SocketAddr::new(Ipv4Addr::LOCALHOST.into(), port);
+        let v6 = |port| SocketAddr::new(Ipv6Addr::LOCALHOST.into(), port);
+        assert_eq!(loopback_addresses("http://127.0.0.1:3210").unwrap(), vec![v4(3210)]);
+        assert_eq!(loopback_addresses("127.0.0.1:3210").unwrap(), vec![v4(3210)]);
+        assert_eq!(loopback_addresses("http://[::1]:3210/api").unwrap(), vec![v6(3210)]);
+        assert_eq!(loopback_addresses("localhost:3210").unwrap(), vec![v4(3210), v6(3210)]);
+        assert_eq!(loopback_addresses("http://localhost").unwrap(), vec![v4(80), v6(80)]);
+
+        for endpoint in ["http://10.0.0.5:22", "192.168.1.1:8080", "https://example.com", "localhost.evil.test:80"] {
+            let error = loopback_addresses(endpoint).unwrap_err();
+            assert!(error.contains("is not a loopback address"), "{endpoint}: {error}");
+        }
+        assert!(loopback_addresses("not a url").is_err());
+    }
+}
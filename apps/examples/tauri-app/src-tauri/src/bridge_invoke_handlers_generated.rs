@@ -2,7 +2,7 @@
 // Source: @zakstam/codex-local-component/host/tauri
 
 macro_rules! bridge_generate_handler {
-    () => {
+    ($($host_command:ident),* $(,)?) => {
         tauri::generate_handler![
             start_bridge,
             open_thread,
@@ -21,6 +21,7 @@ macro_rules! bridge_generate_handler {
             set_disabled_tools,
             stop_bridge,
             get_bridge_state,
+            $($host_command,)*
         ]
     };
 }
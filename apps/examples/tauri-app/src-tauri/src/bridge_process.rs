@@ -1,16 +1,46 @@
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStdin, Command};
-use tokio::sync::Mutex;
+use tokio::sync::{oneshot, Mutex};
 use tokio::time::{timeout, Duration};
 
+#[cfg(feature = "approval-policy")]
+use crate::approval_policy::{self, ApprovalPolicy};
+use crate::approval_request::ApprovalRequest;
+#[cfg(feature = "audit-log")]
+use crate::approval_request::ApprovalKind;
+#[cfg(feature = "audit-log")]
+use crate::audit_log::{AuditRecord, SharedAuditLog};
 use crate::bridge_dispatch_generated::helper_command_for_tauri_command;
+use crate::diagnostics_bundle::DiagnosticsRecorder;
+use crate::event_routing::EventRouter;
+use crate::generation_waiter::GenerationWaiter;
+use crate::helper_heartbeat::{HeartbeatConfig, UNRESPONSIVE_ERROR_CODE};
+use crate::helper_hot_reload::{self, HotReloadConfig};
+use crate::helper_integrity::{self, IntegrityStatus};
+use crate::helper_resolution::{self, CandidateRuntime};
+use crate::helper_process_policy::{HelperProcessConfig, HelperProcessPolicy};
+use crate::helper_readiness::{self, StartFailure, StartOutcome, StartStage};
+use crate::helper_shutdown::{ShutdownConfig, StopPhase, StopReport};
+use crate::host_config::HostConfig;
+use crate::host_events;
+use crate::instance_lock::SessionLock;
+#[cfg(feature = "node-helper")]
+use crate::node_runtime::{self, NodeRuntimeError};
+use crate::payload_schema::validate_command_payload;
+use crate::process_tree::{self, HelperPidFile};
+use crate::redaction::{redact_text, redact_value, redacted};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS), ts(optional_fields = nullable))]
 #[serde(rename_all = "camelCase")]
 pub struct ActorContext {
     pub user_id: Option<String>,
@@ -31,10 +61,24 @@ pub struct HelperStartPayload {
     pub thread_id: Option<String>,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct HelperLaunchOptions {
+    pub process: Option<HelperProcessConfig>,
+    pub ready_timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ResolvedLaunchOptions {
+    process: Option<HelperProcessConfig>,
+    ready_timeout: Duration,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 #[serde(rename_all = "camelCase")]
 pub struct BridgeStateSnapshot {
     pub running: bool,
+    pub phase: Option<String>,
     pub persisted_thread_id: Option<String>,
     pub runtime_thread_id: Option<String>,
     pub local_thread_id: Option<String>,
@@ -43,114 +87,556 @@ pub struct BridgeStateSnapshot {
     pub last_error_code: Option<String>,
     pub last_error: Option<String>,
     pub disabled_tools: Vec<String>,
+    #[cfg_attr(test, ts(as = "Option<f64>"))]
     pub pending_server_request_count: Option<u64>,
+    #[cfg_attr(test, ts(as = "Option<f64>"))]
     pub ingest_enqueued_event_count: Option<u64>,
+    #[cfg_attr(test, ts(as = "Option<f64>"))]
     pub ingest_skipped_event_count: Option<u64>,
     pub ingest_enqueued_by_kind: Option<Vec<IngestMetricEntry>>,
     pub ingest_skipped_by_kind: Option<Vec<IngestMetricEntry>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 #[serde(rename_all = "camelCase")]
 pub struct IngestMetricEntry {
     pub kind: String,
+    #[cfg_attr(test, ts(as = "f64"))]
     pub count: u64,
 }
 
-#[derive(Default)]
-pub struct AppBridgeState {
-    pub runtime: BridgeRuntime,
-}
-
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct BridgeRuntime {
+    session_id: String,
     inner: Arc<Mutex<Option<BridgeProcess>>>,
     snapshot: Arc<Mutex<BridgeStateSnapshot>>,
+    #[cfg(feature = "approval-policy")]
+    approval_policy: Arc<Mutex<Option<ApprovalPolicy>>>,
+    pending_approvals: Arc<Mutex<HashMap<String, ApprovalRequest>>>,
+    start_payload: Arc<Mutex<Option<HelperStartPayload>>>,
+    launch_options: Arc<Mutex<ResolvedLaunchOptions>>,
+    start_waiter: Arc<GenerationWaiter<StartOutcome>>,
+    start_stage: Arc<Mutex<StartStage>>,
+    stop_waiter: Arc<GenerationWaiter<()>>,
+    session_lock: Arc<Mutex<Option<SessionLock>>>,
+    process_generation: Arc<AtomicU64>,
+    last_state_at_ms: Arc<AtomicU64>,
 }
 
 struct BridgeProcess {
     stdin: ChildStdin,
     child: Child,
+    pid: Option<u32>,
+    generation: u64,
+    // Helper script (node modes) or binary, watched for hot reload.
+    script: PathBuf,
 }
 
+#[derive(Default)]
 struct HelperLaunchSpec {
     command: PathBuf,
     args: Vec<String>,
     mode: &'static str,
+    // Only read by the Node.js runtime probe.
+    #[cfg_attr(not(feature = "node-helper"), allow(dead_code))]
+    runtime: CandidateRuntime,
+    cwd: Option<PathBuf>,
+    process_policy: HelperProcessPolicy,
+}
+
+impl HelperLaunchSpec {
+    // The file that actually runs: the script in node modes, otherwise the binary.
+    fn script(&self) -> PathBuf {
+        self.args.first().map(PathBuf::from).unwrap_or_else(|| self.command.clone())
+    }
 }
 
 impl BridgeRuntime {
-    pub async fn start(&self, app: AppHandle, payload: HelperStartPayload) -> Result<(), String> {
-        let has_running = { self.inner.lock().await.is_some() };
-        if has_running {
+    pub fn for_session(session_id: impl Into<String>) -> Self {
+        Self {
+            session_id: session_id.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    // Every event carries the session it came from and is routed to the windows
+    // subscribed to that session (see `EventRouter`).
+    fn emit(&self, app: &AppHandle, event: &str, mut payload: serde_json::Value) -> tauri::Result<()> {
+        if let Some(object) = payload.as_object_mut() {
+            if !self.session_id.is_empty() {
+                object.insert("sessionId".to_string(), json!(self.session_id));
+            }
+        }
+        if let Some(recorder) = app.try_state::<DiagnosticsRecorder>() {
+            recorder.record_event(event, &payload);
+        }
+        match app.try_state::<EventRouter>() {
+            Some(router) => router.emit(app, event, &self.session_id, payload),
+            None => app.emit(event, payload),
+        }
+    }
+
+    pub async fn start(
+        &self,
+        app: AppHandle,
+        payload: HelperStartPayload,
+        options: HelperLaunchOptions,
+    ) -> Result<(), String> {
+        self.reload_approval_policy(&app).await?;
+        let ready_timeout = helper_readiness::ready_timeout(options.ready_timeout_ms)?;
+        if let Some(recorder) = app.try_state::<DiagnosticsRecorder>() {
+            recorder.record_session_config(
+                &self.session_id,
+                json!({
+                    "payload": payload,
+                    "readyTimeoutMs": ready_timeout.as_millis(),
+                    "helperProcess": options.process,
+                }),
+            );
+        }
+        {
+            let mut start_payload = self.start_payload.lock().await;
+            *start_payload = Some(payload.clone());
+        }
+        {
+            let mut launch_options = self.launch_options.lock().await;
+            *launch_options = ResolvedLaunchOptions {
+                process: options.process,
+                ready_timeout,
+            };
+        }
+
+        let running_generation = self.inner.lock().await.as_ref().map(|process| process.generation);
+        if let Some(generation) = running_generation {
+            let ready = self.begin_start_wait(generation).await;
             if self.send_to_helper(&app, "start", json!(payload)).await.is_ok() {
-                return Ok(());
+                return self.await_start_ready(&app, generation, ready).await;
             }
+            self.start_waiter.cancel(generation).await;
             {
                 let mut stale = self.inner.lock().await;
                 *stale = None;
             }
         }
 
-        let helper = resolve_helper_launch_spec(&app)?;
+        self.acquire_session_lock(&app).await?;
+        self.spawn_helper(app, payload).await
+    }
+
+    // Keeps a second app process from starting the same session while this one owns it.
+    async fn acquire_session_lock(&self, app: &AppHandle) -> Result<(), String> {
+        if self.session_id.is_empty() {
+            return Ok(());
+        }
+        let mut session_lock = self.session_lock.lock().await;
+        if session_lock.is_none() {
+            *session_lock = Some(SessionLock::acquire(app, &self.session_id)?);
+        }
+        Ok(())
+    }
+
+    // Boxed because the heartbeat task it spawns calls back into it to restart
+    // an unresponsive helper.
+    fn spawn_helper(
+        &self,
+        app: AppHandle,
+        payload: HelperStartPayload,
+    ) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + '_>> {
+        Box::pin(async move {
+            // The wait exists before the process does, so a helper that dies right
+            // after spawning fails the start instead of running into the timeout.
+            let generation = self.process_generation.fetch_add(1, Ordering::Relaxed) + 1;
+            let ready = self.begin_start_wait(generation).await;
+            if let Err(message) = self.launch_helper_process(&app, &payload, generation).await {
+                self.start_waiter.cancel(generation).await;
+                return Err(self.fail_start(&app, StartFailure::new(StartStage::Spawn, message)));
+            }
+            if let Err(message) = self.send_to_helper(&app, "start", json!(payload)).await {
+                self.start_waiter.cancel(generation).await;
+                return Err(self.fail_start(&app, StartFailure::new(StartStage::Handshake, message)));
+            }
+            self.await_start_ready(&app, generation, ready).await?;
+            self.spawn_hot_reload_watch(app.clone(), generation);
+            self.spawn_heartbeat(app, generation);
+            Ok(())
+        })
+    }
+
+    async fn launch_helper_process(
+        &self,
+        app: &AppHandle,
+        payload: &HelperStartPayload,
+        generation: u64,
+    ) -> Result<(), String> {
+        let process_config = self.launch_options.lock().await.process.clone();
+        let mut helper = resolve_helper_launch_spec(app)?;
+        #[cfg(feature = "node-helper")]
+        if helper.runtime == CandidateRuntime::Node {
+            let node = helper.command.clone();
+            let checked = tokio::task::spawn_blocking(move || node_runtime::check_node(&node))
+                .await
+                .map_err(|e| format!("node runtime probe failed: {e}"))?;
+            if let Err(error) = checked {
+                self.record_node_runtime_error(app, &error).await;
+                return Err(error.to_string());
+            }
+        }
+        helper.cwd = payload.cwd.as_ref().map(PathBuf::from);
+        helper.process_policy = HelperProcessPolicy::resolve(process_config.as_ref())?;
+        let allow_unverified = app.state::<HostConfig>().allow_unverified_helper;
+        if helper_integrity::verify_helper(&helper.script(), allow_unverified)? == IntegrityStatus::Unverified {
+            let _ = self.emit(
+                app,
+                host_events::GLOBAL_MESSAGE,
+                json!({
+                    "kind": "bridge/helper_unverified",
+                    "path": helper.script().to_string_lossy(),
+                    "tsMs": crate::now_unix_ms(),
+                }),
+            );
+        }
         let mut command = Command::new(&helper.command);
         for arg in &helper.args {
             command.arg(arg);
         }
+        if let Some(cwd) = &helper.cwd {
+            command.current_dir(cwd);
+        }
+        helper.process_policy.apply(&mut command);
+        process_tree::isolate_process_tree(&mut command);
         command.stdin(std::process::Stdio::piped());
         command.stdout(std::process::Stdio::piped());
         command.stderr(std::process::Stdio::piped());
 
-        let mut child = command.spawn().map_err(|e| format!("failed to spawn helper: {e}"))?;
+        let mut child = process_tree::spawn(command)
+            .await
+            .map_err(|e| format!("failed to spawn helper: {e}"))?;
+        let pid = child.id();
+        if let Some(pid) = pid {
+            if let Err(message) = HelperPidFile::for_app(app).and_then(|pid_file| pid_file.record(pid)) {
+                let _ = self.emit(app, host_events::PROTOCOL_ERROR, json!({ "message": message }));
+            }
+        }
         let stdin = child.stdin.take().ok_or_else(|| "helper stdin unavailable".to_string())?;
         let stdout = child.stdout.take().ok_or_else(|| "helper stdout unavailable".to_string())?;
         let stderr = child.stderr.take().ok_or_else(|| "helper stderr unavailable".to_string())?;
 
         {
-            let snapshot = self.snapshot.clone();
+            let runtime = self.clone();
             let app_handle = app.clone();
             tokio::spawn(async move {
                 let mut lines = BufReader::new(stdout).lines();
                 while let Ok(Some(line)) = lines.next_line().await {
-                    handle_helper_line(&app_handle, &snapshot, &line).await;
+                    handle_helper_line(&app_handle, &runtime, generation, &line).await;
                 }
+                runtime.helper_output_closed(generation).await;
             });
         }
 
         {
-            let snapshot = self.snapshot.clone();
+            let runtime = self.clone();
             let app_handle = app.clone();
             tokio::spawn(async move {
                 let mut lines = BufReader::new(stderr).lines();
                 while let Ok(Some(line)) = lines.next_line().await {
                     if let Some(raw_line) = line.strip_prefix("[codex-bridge:raw-in] ") {
-                        let _ = app_handle.emit(
-                            "codex:global_message",
-                            json!({ "kind": "protocol/raw_in", "line": raw_line }),
+                        let _ = runtime.emit(
+                            &app_handle,
+                            host_events::GLOBAL_MESSAGE,
+                            json!({ "kind": "protocol/raw_in", "line": redact_text(raw_line) }),
                         );
                         continue;
                     }
+                    let line = redact_text(&line);
                     {
-                        let mut next = snapshot.lock().await;
+                        let mut next = runtime.snapshot.lock().await;
                         next.last_error = Some(line.clone());
                     }
-                    let _ = app_handle.emit("codex:protocol_error", json!({ "message": line }));
+                    let _ = runtime.emit(&app_handle, host_events::PROTOCOL_ERROR, json!({ "message": line }));
                 }
             });
         }
 
         {
             let mut inner = self.inner.lock().await;
-            *inner = Some(BridgeProcess { stdin, child });
+            *inner = Some(BridgeProcess {
+                stdin,
+                child,
+                pid,
+                generation,
+                script: helper.script(),
+            });
         }
         {
             let mut snap = self.snapshot.lock().await;
             snap.running = true;
         }
-        app.emit("codex:bridge_state", json!({ "running": true, "helperMode": helper.mode }))
+        self.emit(app, host_events::BRIDGE_STATE, json!({ "running": true, "helperMode": helper.mode }))
             .map_err(|e| format!("emit failed: {e}"))?;
 
-        self.send_to_helper(&app, "start", json!(payload)).await
+        Ok(())
+    }
+
+    // A restart or hot reload tears the old helper down and spawns the next one
+    // before the old stdout reader sees EOF, so only waits of this generation end
+    // here; the respawned helper's are its own.
+    async fn helper_output_closed(&self, generation: u64) {
+        let stage = *self.start_stage.lock().await;
+        self.start_waiter
+            .resolve(generation, Err(StartFailure::new(stage, "helper exited before reporting ready")))
+            .await;
+        // Dropping the sender ends a pending stop-ack wait early.
+        self.stop_waiter.cancel(generation).await;
+    }
+
+    async fn begin_start_wait(&self, generation: u64) -> oneshot::Receiver<StartOutcome> {
+        *self.start_stage.lock().await = StartStage::Handshake;
+        self.start_waiter.begin(generation).await
+    }
+
+    async fn await_start_ready(
+        &self,
+        app: &AppHandle,
+        generation: u64,
+        ready: oneshot::Receiver<StartOutcome>,
+    ) -> Result<(), String> {
+        let ready_timeout = self.launch_options.lock().await.ready_timeout;
+        let outcome = match timeout(ready_timeout, ready).await {
+            Ok(Ok(outcome)) => outcome,
+            Ok(Err(_)) => Err(StartFailure::new(
+                *self.start_stage.lock().await,
+                "start was superseded by another start request",
+            )),
+            Err(_) => {
+                self.start_waiter.cancel(generation).await;
+                Err(StartFailure::timed_out(*self.start_stage.lock().await, ready_timeout))
+            }
+        };
+        let Err(failure) = outcome else {
+            return Ok(());
+        };
+        // A helper that never answered is not trusted with the next start.
+        if failure.timed_out {
+            let process = self.inner.lock().await.take();
+            if let Some(mut process) = process {
+                self.teardown_process(app, &mut process).await;
+            }
+            self.record_helper_disconnect(app, failure.to_string()).await;
+        }
+        Err(self.fail_start(app, failure))
+    }
+
+    fn fail_start(&self, app: &AppHandle, failure: StartFailure) -> String {
+        let _ = self.emit(
+            app,
+            host_events::GLOBAL_MESSAGE,
+            json!({
+                "kind": "bridge/start_failed",
+                "stage": failure.stage,
+                "message": redact_text(&failure.message),
+                "timedOut": failure.timed_out,
+                "tsMs": crate::now_unix_ms(),
+            }),
+        );
+        failure.to_string()
+    }
+
+    fn spawn_heartbeat(&self, app: AppHandle, generation: u64) {
+        let config = match HeartbeatConfig::from_env() {
+            Ok(Some(config)) => config,
+            Ok(None) => return,
+            Err(message) => {
+                let _ = self.emit(&app, host_events::PROTOCOL_ERROR, json!({ "message": message }));
+                return;
+            }
+        };
+        let runtime = self.clone();
+        tokio::spawn(async move { runtime.run_heartbeat(app, generation, config).await });
+    }
+
+    // Every `status` command makes the helper emit a `state` line, so a ping is
+    // answered when a state arrives after it was sent. The ping goes through the
+    // helper's command queue, which also surfaces commands stuck behind it.
+    async fn run_heartbeat(&self, app: AppHandle, generation: u64, config: HeartbeatConfig) {
+        let mut misses = 0;
+        loop {
+            tokio::time::sleep(config.interval).await;
+            if !self.is_current_process(generation).await {
+                return;
+            }
+            let ping_sent_at = crate::now_unix_ms() as u64;
+            // A wedged helper can stop draining stdin; bound the write so the
+            // heartbeat does not block on the pipe (and the process lock) forever.
+            match timeout(config.timeout, self.send_to_helper(&app, "status", json!({}))).await {
+                Ok(Err(_)) => return,
+                Ok(Ok(())) => tokio::time::sleep(config.timeout).await,
+                Err(_) => {}
+            }
+            if !self.is_current_process(generation).await {
+                return;
+            }
+            if self.last_state_at_ms.load(Ordering::Relaxed) >= ping_sent_at {
+                misses = 0;
+                continue;
+            }
+            misses += 1;
+            if misses < config.max_misses {
+                continue;
+            }
+            self.mark_helper_unresponsive(&app, misses, config.timeout).await;
+            if config.restart {
+                self.restart_helper(&app, generation, UNRESPONSIVE_ERROR_CODE).await;
+            }
+            return;
+        }
+    }
+
+    pub async fn has_process(&self) -> bool {
+        self.inner.lock().await.is_some()
+    }
+
+    async fn is_current_process(&self, generation: u64) -> bool {
+        self.inner
+            .lock()
+            .await
+            .as_ref()
+            .is_some_and(|process| process.generation == generation)
+    }
+
+    #[cfg(feature = "node-helper")]
+    async fn record_node_runtime_error(&self, app: &AppHandle, error: &NodeRuntimeError) {
+        let state = {
+            let mut snapshot = self.snapshot.lock().await;
+            snapshot.phase = Some("error".to_string());
+            snapshot.last_error_code = Some(error.code.as_str().to_string());
+            snapshot.last_error = Some(error.message.clone());
+            snapshot.clone()
+        };
+        let _ = self.emit(app, host_events::BRIDGE_STATE, json!(state));
+    }
+
+    async fn mark_helper_unresponsive(&self, app: &AppHandle, misses: u32, deadline: Duration) {
+        let state = {
+            let mut snapshot = self.snapshot.lock().await;
+            snapshot.phase = Some("error".to_string());
+            snapshot.last_error_code = Some(UNRESPONSIVE_ERROR_CODE.to_string());
+            snapshot.last_error = Some(format!(
+                "bridge helper missed {misses} consecutive status pings ({}ms deadline each)",
+                deadline.as_millis()
+            ));
+            snapshot.clone()
+        };
+        let _ = self.emit(app, host_events::BRIDGE_STATE, json!(state));
+    }
+
+    // Returns whether a new helper is up; a helper that was already replaced is left alone.
+    async fn restart_helper(&self, app: &AppHandle, generation: u64, reason: &str) -> bool {
+        let process = {
+            let mut inner = self.inner.lock().await;
+            match inner.as_ref() {
+                Some(process) if process.generation == generation => inner.take(),
+                _ => None,
+            }
+        };
+        let Some(mut process) = process else {
+            return false;
+        };
+        self.teardown_process(app, &mut process).await;
+        self.pending_approvals.lock().await.clear();
+
+        let Some(payload) = self.start_payload.lock().await.clone() else {
+            return false;
+        };
+        let _ = self.emit(
+            app,
+            host_events::GLOBAL_MESSAGE,
+            json!({ "kind": "bridge/helper_restart", "reason": reason, "tsMs": crate::now_unix_ms() }),
+        );
+        match self.spawn_helper(app.clone(), payload).await {
+            Ok(()) => true,
+            Err(message) => {
+                self.record_helper_disconnect(app, format!("helper restart failed: {message}")).await;
+                false
+            }
+        }
+    }
+
+    fn spawn_hot_reload_watch(&self, app: AppHandle, generation: u64) {
+        let config = match HotReloadConfig::from_env() {
+            Ok(Some(config)) => config,
+            Ok(None) => return,
+            Err(message) => {
+                let _ = self.emit(&app, host_events::PROTOCOL_ERROR, json!({ "message": message }));
+                return;
+            }
+        };
+        let runtime = self.clone();
+        tokio::spawn(async move {
+            runtime.run_hot_reload_watch(app, generation, config).await;
+        });
+    }
+
+    async fn run_hot_reload_watch(&self, app: AppHandle, generation: u64, config: HotReloadConfig) {
+        let script = match self.inner.lock().await.as_ref() {
+            Some(process) if process.generation == generation => process.script.clone(),
+            _ => return,
+        };
+        let baseline = helper_hot_reload::fingerprint(&script);
+        let mut pending = None;
+        loop {
+            tokio::time::sleep(config.interval).await;
+            if !self.is_current_process(generation).await {
+                return;
+            }
+            let current = helper_hot_reload::fingerprint(&script);
+            if current == baseline {
+                pending = None;
+                continue;
+            }
+            // Reload only after the file has been stable for one interval, so a
+            // build that is still writing it is not picked up half-done.
+            if current.is_none() || pending != current {
+                pending = current;
+                continue;
+            }
+            self.hot_reload_helper(&app, generation, &script).await;
+            return;
+        }
+    }
+
+    async fn hot_reload_helper(&self, app: &AppHandle, generation: u64, script: &Path) {
+        let resume_thread = self.snapshot.lock().await.runtime_thread_id.clone();
+        let restarted = self.restart_helper(app, generation, "helper_changed").await;
+        let mut resumed_thread_id = None;
+        let mut error = None;
+        if restarted {
+            if let Some(thread_id) = resume_thread {
+                match self
+                    .send_to_helper(app, "open_thread", json!({ "strategy": "resume", "threadHandle": thread_id }))
+                    .await
+                {
+                    Ok(()) => resumed_thread_id = Some(thread_id),
+                    Err(message) => error = Some(redact_text(&message)),
+                }
+            }
+        }
+        let _ = self.emit(
+            app,
+            host_events::GLOBAL_MESSAGE,
+            json!({
+                "kind": "bridge/hot_reload",
+                "path": script.to_string_lossy(),
+                "status": if restarted { "restarted" } else { "failed" },
+                "resumedThreadId": resumed_thread_id,
+                "error": error,
+                "tsMs": crate::now_unix_ms(),
+            }),
+        );
     }
 
     pub async fn forward_tauri_json_command(
@@ -162,32 +648,69 @@ impl BridgeRuntime {
         self.forward_tauri_command(&app, tauri_command, payload).await
     }
 
-    pub async fn stop(&self, app: AppHandle) -> Result<(), String> {
+    pub async fn stop(&self, app: AppHandle, config: ShutdownConfig) -> Result<StopReport, String> {
+        let started = std::time::Instant::now();
+        let mut report = StopReport::default();
         let process = {
             let mut inner = self.inner.lock().await;
             inner.take()
         };
         if let Some(mut process) = process {
-            let line = json!({ "type": "stop", "payload": {} }).to_string();
-            let _ = process.stdin.write_all(line.as_bytes()).await;
-            let _ = process.stdin.write_all(b"\n").await;
-            let _ = process.stdin.flush().await;
-            let waited = timeout(Duration::from_millis(1200), process.child.wait()).await;
-            let needs_force_kill = match waited {
-                Ok(Ok(_)) => false,
-                Ok(Err(_)) => true,
-                Err(_) => true,
+            report.was_running = true;
+            let turn_active = self.snapshot.lock().await.turn_id.is_some();
+            if config.interrupt_first && turn_active {
+                self.emit_stop_progress(&app, "interrupt", "started");
+                let status = if write_helper_command(&mut process.stdin, "interrupt").await.is_err() {
+                    "failed"
+                } else if self.wait_for_turn_settled(config.interrupt_timeout).await {
+                    report.interrupted = true;
+                    "completed"
+                } else {
+                    report.record_timeout(StopPhase::Interrupt);
+                    "timed_out"
+                };
+                self.emit_stop_progress(&app, "interrupt", status);
+            }
+
+            // The helper acks `stop` only after its runtime has flushed the ingest queue to Convex.
+            self.emit_stop_progress(&app, "ack", "started");
+            let ack = self.stop_waiter.begin(process.generation).await;
+            let status = if write_helper_command(&mut process.stdin, "stop").await.is_err() {
+                "failed"
+            } else {
+                match timeout(config.ack_timeout, ack).await {
+                    Ok(Ok(())) => {
+                        report.acknowledged = true;
+                        "completed"
+                    }
+                    Ok(Err(_)) => "failed",
+                    Err(_) => {
+                        report.record_timeout(StopPhase::Ack);
+                        "timed_out"
+                    }
+                }
             };
-            if needs_force_kill {
-                let _ = process.child.kill().await;
-                let _ = timeout(Duration::from_millis(500), process.child.wait()).await;
+            self.stop_waiter.cancel(process.generation).await;
+            self.emit_stop_progress(&app, "ack", status);
+
+            self.emit_stop_progress(&app, "exit", "started");
+            report.exited = timeout(config.exit_timeout, process.child.wait()).await.is_ok();
+            if !report.exited {
+                report.record_timeout(StopPhase::Exit);
             }
+            self.emit_stop_progress(&app, "exit", if report.exited { "completed" } else { "timed_out" });
+            // Runs even after a clean exit so grandchildren left in the group are reaped.
+            self.teardown_process(&app, &mut process).await;
         }
+        self.pending_approvals.lock().await.clear();
+        self.session_lock.lock().await.take();
+        report.elapsed_ms = started.elapsed().as_millis() as u64;
 
         let mut snapshot = self.snapshot.lock().await;
         *snapshot = BridgeStateSnapshot::default();
-        let _ = app.emit(
-            "codex:bridge_state",
+        let _ = self.emit(
+            &app,
+            host_events::BRIDGE_STATE,
             json!({
                 "running": false,
                 "persistedThreadId": null,
@@ -205,13 +728,134 @@ impl BridgeRuntime {
                 "lastError": null
             }),
         );
-        Ok(())
+        let _ = self.emit(
+            &app,
+            host_events::GLOBAL_MESSAGE,
+            json!({ "kind": "bridge/stop_completed", "report": report, "tsMs": crate::now_unix_ms() }),
+        );
+        Ok(report)
+    }
+
+    fn emit_stop_progress(&self, app: &AppHandle, phase: &str, status: &str) {
+        let _ = self.emit(
+            app,
+            host_events::GLOBAL_MESSAGE,
+            json!({
+                "kind": "bridge/stop_progress",
+                "phase": phase,
+                "status": status,
+                "tsMs": crate::now_unix_ms(),
+            }),
+        );
+    }
+
+    async fn wait_for_turn_settled(&self, deadline: Duration) -> bool {
+        timeout(deadline, async {
+            while self.snapshot.lock().await.turn_id.is_some() {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .is_ok()
     }
 
     pub async fn snapshot(&self) -> BridgeStateSnapshot {
         self.snapshot.lock().await.clone()
     }
 
+    pub async fn start_cwd(&self) -> Option<String> {
+        self.start_payload.lock().await.as_ref().and_then(|payload| payload.cwd.clone())
+    }
+
+    #[cfg(feature = "approval-policy")]
+    async fn reload_approval_policy(&self, app: &AppHandle) -> Result<(), String> {
+        let loaded = approval_policy::load_policy(app)?;
+        if let Some((policy, path)) = &loaded {
+            let _ = self.emit(
+                app,
+                host_events::GLOBAL_MESSAGE,
+                json!({
+                    "kind": "approval/policy_loaded",
+                    "path": path.to_string_lossy(),
+                    "ruleCount": policy.rules.len(),
+                }),
+            );
+        }
+        let mut current = self.approval_policy.lock().await;
+        *current = loaded.map(|(policy, _)| policy);
+        Ok(())
+    }
+
+    // Without the policy engine every approval request goes to the UI.
+    #[cfg(not(feature = "approval-policy"))]
+    async fn reload_approval_policy(&self, _app: &AppHandle) -> Result<(), String> {
+        Ok(())
+    }
+
+    // Returns true when the policy answered the request. The helper still
+    // persists it to Convex and emits it on `codex:event`; the response resolves
+    // it there.
+    async fn handle_approval_request(&self, app: &AppHandle, payload: &serde_json::Value) -> bool {
+        let Ok(request) = serde_json::from_value::<ApprovalRequest>(payload.clone()) else {
+            return false;
+        };
+        {
+            let mut pending = self.pending_approvals.lock().await;
+            pending.insert(request.request_id.to_string(), request.clone());
+        }
+        self.apply_approval_policy(app, &request).await
+    }
+
+    #[cfg(feature = "approval-policy")]
+    async fn apply_approval_policy(&self, app: &AppHandle, request: &ApprovalRequest) -> bool {
+        let policy_match = {
+            let policy = self.approval_policy.lock().await;
+            match policy.as_ref() {
+                Some(policy) => policy.evaluate(request),
+                None => None,
+            }
+        };
+        let Some(policy_match) = policy_match else {
+            return false;
+        };
+
+        let decision = policy_match.action.decision();
+        let mut error = None;
+        if let (Some(decision), Some(helper_command)) = (decision, request.helper_command()) {
+            let response = json!({ "requestId": request.request_id, "decision": decision });
+            match self.send_to_helper(app, helper_command, response.clone()).await {
+                Ok(()) => {
+                    self.record_decision(app, helper_command, &response, Some(policy_match.rule_id.clone()))
+                        .await;
+                }
+                Err(message) => error = Some(message),
+            }
+        }
+        let _ = self.emit(
+            app,
+            host_events::GLOBAL_MESSAGE,
+            json!({
+                "kind": "approval/policy_decision",
+                "method": request.method,
+                "requestId": request.request_id,
+                "threadId": request.thread_id,
+                "turnId": request.turn_id,
+                "itemId": request.item_id,
+                "ruleId": policy_match.rule_id,
+                "action": policy_match.action,
+                "decision": decision,
+                "automated": decision.is_some() && error.is_none(),
+                "error": error,
+            }),
+        );
+        decision.is_some() && error.is_none()
+    }
+
+    #[cfg(not(feature = "approval-policy"))]
+    async fn apply_approval_policy(&self, _app: &AppHandle, _request: &ApprovalRequest) -> bool {
+        false
+    }
+
     async fn forward_tauri_command(
         &self,
         app: &AppHandle,
@@ -220,7 +864,88 @@ impl BridgeRuntime {
     ) -> Result<(), String> {
         let helper_command = helper_command_for_tauri_command(tauri_command)
             .ok_or_else(|| format!("No helper mapping configured for tauri command: {tauri_command}"))?;
-        self.send_to_helper(app, helper_command, payload).await
+        validate_command_payload(tauri_command, &payload)?;
+        self.send_to_helper(app, helper_command, payload.clone()).await?;
+        if matches!(
+            helper_command,
+            "respond_command_approval" | "respond_file_change_approval" | "respond_tool_user_input"
+        ) {
+            self.record_decision(app, helper_command, &payload, None).await;
+        }
+        Ok(())
+    }
+
+    async fn take_pending_approval(&self, payload: &serde_json::Value) -> (serde_json::Value, Option<ApprovalRequest>) {
+        let request_id = payload.get("requestId").cloned().unwrap_or(serde_json::Value::Null);
+        let request = self.pending_approvals.lock().await.remove(&request_id.to_string());
+        (request_id, request)
+    }
+
+    #[cfg(not(feature = "audit-log"))]
+    async fn record_decision(
+        &self,
+        _app: &AppHandle,
+        _helper_command: &str,
+        payload: &serde_json::Value,
+        _rule_id: Option<String>,
+    ) {
+        self.take_pending_approval(payload).await;
+    }
+
+    #[cfg(feature = "audit-log")]
+    async fn record_decision(
+        &self,
+        app: &AppHandle,
+        helper_command: &str,
+        payload: &serde_json::Value,
+        rule_id: Option<String>,
+    ) {
+        let (request_id, request) = self.take_pending_approval(payload).await;
+        let (actor_user_id, session_id) = {
+            let start_payload = self.start_payload.lock().await;
+            match start_payload.as_ref() {
+                Some(start) => (start.actor.user_id.clone(), Some(start.session_id.clone())),
+                None => (None, None),
+            }
+        };
+        let (decision, summary) = if helper_command == "respond_tool_user_input" {
+            let answers = payload.get("answers").cloned().unwrap_or(serde_json::Value::Null);
+            let questions = answers
+                .as_object()
+                .map(|answers| answers.keys().cloned().collect::<Vec<_>>().join(", "))
+                .unwrap_or_default();
+            (answers, format!("answered tool input questions: {questions}"))
+        } else {
+            (
+                payload.get("decision").cloned().unwrap_or(serde_json::Value::Null),
+                request
+                    .as_ref()
+                    .map(summarize_approval_request)
+                    .unwrap_or_else(|| "approval request details unavailable".to_string()),
+            )
+        };
+
+        let record = AuditRecord {
+            command: helper_command.to_string(),
+            actor_user_id,
+            session_id,
+            request_id,
+            method: request.map(|request| request.method),
+            summary: redact_text(&summary),
+            decision: redacted(&decision),
+            automated: rule_id.is_some(),
+            rule_id,
+        };
+        let Some(audit_log) = app.try_state::<SharedAuditLog>() else {
+            return;
+        };
+        if let Err(message) = audit_log.append(app, record).await {
+            let _ = self.emit(
+                app,
+                host_events::PROTOCOL_ERROR,
+                json!({ "message": format!("audit log append failed: {message}") }),
+            );
+        }
     }
 
     async fn send_to_helper(&self, app: &AppHandle, command: &str, payload: serde_json::Value) -> Result<(), String> {
@@ -231,24 +956,21 @@ impl BridgeRuntime {
 
         let line = json!({ "type": command, "payload": payload }).to_string();
         if let Err(error) = process.stdin.write_all(line.as_bytes()).await {
-            let _ = process.child.kill().await;
-            let _ = timeout(Duration::from_millis(500), process.child.wait()).await;
+            self.teardown_process(app, process).await;
             *inner = None;
             self.record_helper_disconnect(app, format!("failed to write command: {error}"))
                 .await;
             return Err(format!("failed to write command: {error}"));
         }
         if let Err(error) = process.stdin.write_all(b"\n").await {
-            let _ = process.child.kill().await;
-            let _ = timeout(Duration::from_millis(500), process.child.wait()).await;
+            self.teardown_process(app, process).await;
             *inner = None;
             self.record_helper_disconnect(app, format!("failed to write newline: {error}"))
                 .await;
             return Err(format!("failed to write newline: {error}"));
         }
         if let Err(error) = process.stdin.flush().await {
-            let _ = process.child.kill().await;
-            let _ = timeout(Duration::from_millis(500), process.child.wait()).await;
+            self.teardown_process(app, process).await;
             *inner = None;
             self.record_helper_disconnect(app, format!("failed to flush helper stdin: {error}"))
                 .await;
@@ -257,7 +979,15 @@ impl BridgeRuntime {
         Ok(())
     }
 
+    async fn teardown_process(&self, app: &AppHandle, process: &mut BridgeProcess) {
+        process_tree::terminate_process_tree(&mut process.child, process.pid, process_tree::TERMINATE_GRACE).await;
+        if let Some(pid) = process.pid {
+            let _ = HelperPidFile::for_app(app).and_then(|pid_file| pid_file.remove(pid));
+        }
+    }
+
     async fn record_helper_disconnect(&self, app: &AppHandle, message: String) {
+        let message = redact_text(&message);
         let disabled_tools = {
             let mut snapshot = self.snapshot.lock().await;
             snapshot.running = false;
@@ -279,8 +1009,10 @@ impl BridgeRuntime {
             let mut inner = self.inner.lock().await;
             *inner = None;
         }
-        let _ = app.emit(
-            "codex:bridge_state",
+        self.pending_approvals.lock().await.clear();
+        let _ = self.emit(
+            app,
+            host_events::BRIDGE_STATE,
             json!({
                 "running": false,
                 "persistedThreadId": null,
@@ -302,115 +1034,34 @@ impl BridgeRuntime {
 }
 
 fn resolve_helper_launch_spec(app: &AppHandle) -> Result<HelperLaunchSpec, String> {
-    if let Ok(path) = std::env::var("CODEX_HELPER_BIN") {
-        let bin_path = resolve_file_command(&path, "CODEX_HELPER_BIN")?;
-        if bin_path.exists() {
-            return Ok(HelperLaunchSpec {
-                command: bin_path,
-                args: vec![],
-                mode: "standalone-binary",
-            });
-        }
-    }
-
-    let cwd = std::env::current_dir().map_err(|e| format!("current_dir failed: {e}"))?;
-    let fallback_js = cwd.join("../dist-node/bridge-helper.js");
-
-    // In tauri dev, the resource directory can hold stale copies from older runs.
-    // Prefer the freshly built local JS helper to keep host/component schemas in sync.
-    if cfg!(debug_assertions) && fallback_js.exists() {
-        let node_bin = resolve_node_command()?;
-        return Ok(HelperLaunchSpec {
-            command: node_bin,
-            args: vec![fallback_js.to_string_lossy().to_string()],
-            mode: "node-js-dev-local",
-        });
-    }
-
-    if let Ok(path) = app
-        .path()
-        .resolve("bridge-helper", tauri::path::BaseDirectory::Resource)
-    {
-        if path.exists() {
-            return Ok(HelperLaunchSpec {
-                command: path,
-                args: vec![],
-                mode: "standalone-binary",
-            });
-        }
-    }
-
-    if let Ok(path) = app
-        .path()
-        .resolve("bridge-helper.exe", tauri::path::BaseDirectory::Resource)
-    {
-        if path.exists() {
-            return Ok(HelperLaunchSpec {
-                command: path,
-                args: vec![],
-                mode: "standalone-binary",
-            });
-        }
-    }
-
-    if let Ok(path) = app
-        .path()
-        .resolve("bridge-helper.js", tauri::path::BaseDirectory::Resource)
-    {
-        if path.exists() {
-            let node_bin = resolve_node_command()?;
-            return Ok(HelperLaunchSpec {
-                command: node_bin,
-                args: vec![path.to_string_lossy().to_string()],
-                mode: "node-js",
-            });
-        }
-    }
-
-    let fallback_bin = cwd.join("../dist-node/bridge-helper");
-    if fallback_bin.exists() {
-        return Ok(HelperLaunchSpec {
-            command: fallback_bin,
-            args: vec![],
-            mode: "standalone-binary",
-        });
-    }
-    let fallback_bin_exe = cwd.join("../dist-node/bridge-helper.exe");
-    if fallback_bin_exe.exists() {
-        return Ok(HelperLaunchSpec {
-            command: fallback_bin_exe,
-            args: vec![],
-            mode: "standalone-binary",
-        });
-    }
-
-    if fallback_js.exists() {
-        let node_bin = resolve_node_command()?;
-        return Ok(HelperLaunchSpec {
-            command: node_bin,
-            args: vec![fallback_js.to_string_lossy().to_string()],
-            mode: "node-js",
-        });
-    }
+    let resolved = helper_resolution::resolve_helper(app)?;
+    Ok(HelperLaunchSpec {
+        command: resolved.command,
+        args: resolved.args,
+        mode: resolved.mode,
+        runtime: resolved.runtime,
+        ..Default::default()
+    })
+}
 
-    Err(
-        "No helper runtime found. Build `dist-node/bridge-helper.js` or provide `CODEX_HELPER_BIN`."
-            .to_string(),
-    )
+async fn write_helper_command(stdin: &mut ChildStdin, command_type: &str) -> std::io::Result<()> {
+    let line = json!({ "type": command_type, "payload": {} }).to_string();
+    stdin.write_all(line.as_bytes()).await?;
+    stdin.write_all(b"\n").await?;
+    stdin.flush().await
 }
 
-async fn handle_helper_line(app: &AppHandle, snapshot: &Arc<Mutex<BridgeStateSnapshot>>, line: &str) {
+async fn handle_helper_line(app: &AppHandle, runtime: &BridgeRuntime, generation: u64, line: &str) {
+    let snapshot = &runtime.snapshot;
     let parsed: serde_json::Value = match serde_json::from_str(line) {
         Ok(value) => value,
         Err(error) => {
+            let message = redact_text(&format!("failed to parse helper line: {error}"));
             {
                 let mut next = snapshot.lock().await;
-                next.last_error = Some(error.to_string());
+                next.last_error = Some(message.clone());
             }
-            let _ = app.emit(
-                "codex:protocol_error",
-                json!({ "message": format!("failed to parse helper line: {error}") }),
-            );
+            let _ = runtime.emit(app, host_events::PROTOCOL_ERROR, json!({ "message": message }));
             return;
         }
     };
@@ -422,28 +1073,52 @@ async fn handle_helper_line(app: &AppHandle, snapshot: &Arc<Mutex<BridgeStateSna
 
     match kind {
         "state" => {
+            runtime
+                .last_state_at_ms
+                .store(crate::now_unix_ms() as u64, Ordering::Relaxed);
             if let Some(payload) = parsed.get("payload") {
-                if let Ok(next_state) = serde_json::from_value::<BridgeStateSnapshot>(payload.clone()) {
+                if let Ok(mut next_state) = serde_json::from_value::<BridgeStateSnapshot>(payload.clone()) {
+                    next_state.last_error = next_state.last_error.as_deref().map(redact_text);
                     {
                         let mut current = snapshot.lock().await;
                         *current = next_state.clone();
                     }
-                    let _ = app.emit("codex:bridge_state", payload.clone());
+                    let _ = runtime.emit(app, host_events::BRIDGE_STATE, redacted(payload));
                 }
             }
         }
         "event" => {
-            if let Some(payload) = parsed.get("payload") {
-                let _ = app.emit("codex:event", payload.clone());
+            if let Some(payload) = helper_event_payload(&parsed) {
+                let _ = runtime.emit(app, host_events::HELPER_EVENT, payload);
             }
         }
         "global" => {
             if let Some(payload) = parsed.get("payload") {
-                let _ = app.emit("codex:global_message", payload.clone());
+                let kind = payload.get("kind").and_then(|value| value.as_str());
+                // Policy evaluation sees the original payload; only the emitted copy is scrubbed.
+                // Only the `approval/requested` notice is suppressed for a request the policy
+                // answered; the request itself still reaches the UI through Convex.
+                let answered = kind == Some("approval/requested") && runtime.handle_approval_request(app, payload).await;
+                if !answered {
+                    let _ = runtime.emit(app, host_events::GLOBAL_MESSAGE, redacted(payload));
+                }
+                if kind == Some("bridge/start_stage") {
+                    if let Some(stage) = payload.get("stage").and_then(|v| v.as_str()).and_then(StartStage::parse) {
+                        *runtime.start_stage.lock().await = stage;
+                    }
+                }
             }
         }
+        "ack" if parsed.pointer("/payload/command").and_then(|v| v.as_str()) == Some("start") => {
+            runtime.start_waiter.resolve(generation, Ok(())).await;
+        }
+        "ack" if parsed.pointer("/payload/command").and_then(|v| v.as_str()) == Some("stop") => {
+            runtime.stop_waiter.resolve(generation, ()).await;
+        }
         "protocol_error" | "error" => {
             if let Some(payload) = parsed.get("payload") {
+                let mut payload = payload.clone();
+                redact_value(&mut payload);
                 let message = payload
                     .get("message")
                     .and_then(|v| v.as_str())
@@ -453,97 +1128,102 @@ async fn handle_helper_line(app: &AppHandle, snapshot: &Arc<Mutex<BridgeStateSna
                     let mut next = snapshot.lock().await;
                     next.last_error = Some(message.clone());
                 }
-                let _ = app.emit("codex:protocol_error", payload.clone());
+                if payload.get("command").and_then(|v| v.as_str()) == Some("start") {
+                    let stage = match payload.get("stage").and_then(|v| v.as_str()).and_then(StartStage::parse) {
+                        Some(stage) => stage,
+                        None => *runtime.start_stage.lock().await,
+                    };
+                    runtime.start_waiter.resolve(generation, Err(StartFailure::new(stage, message))).await;
+                }
+                let _ = runtime.emit(app, host_events::PROTOCOL_ERROR, payload);
             }
         }
         _ => {}
     }
 }
 
-fn resolve_file_command(path: &str, env_name: &str) -> Result<PathBuf, String> {
-    let candidate = path.trim();
-    if candidate.is_empty() {
-        return Err(format!("{env_name} cannot be empty"));
-    }
+// Helper events carry app-server notifications and server requests verbatim,
+// including command output and auth token refreshes.
+fn helper_event_payload(line: &serde_json::Value) -> Option<serde_json::Value> {
+    line.get("payload").map(redacted)
+}
 
-    let candidate_path = Path::new(candidate);
-    if is_explicit_path(candidate_path) {
-        if !candidate_path.exists() {
-            return Err(format!(
-                "{env_name} must reference an existing file. Missing path: {candidate}"
-            ));
+#[cfg(feature = "audit-log")]
+fn summarize_approval_request(request: &ApprovalRequest) -> String {
+    match request.kind() {
+        Some(ApprovalKind::CommandExecution) => {
+            let command = request.command.as_deref().unwrap_or("<unknown command>");
+            match request.cwd.as_deref() {
+                Some(cwd) => format!("{command} (cwd: {cwd})"),
+                None => command.to_string(),
+            }
         }
-        if !candidate_path.is_file() {
-            return Err(format!("{env_name} must reference a file: {candidate}"));
+        Some(ApprovalKind::FileChange) => {
+            let mut summary = format!("{} file(s): {}", request.paths.len(), request.paths.join(", "));
+            if let Some(root) = request.grant_root.as_deref() {
+                summary.push_str(&format!(" (grant root: {root})"));
+            }
+            summary
         }
-        Ok(candidate_path.to_path_buf())
-    } else {
-        Ok(candidate_path.to_path_buf())
+        None => request.method.clone(),
     }
 }
 
-fn resolve_node_command() -> Result<PathBuf, String> {
-    let configured = std::env::var("CODEX_NODE_BIN").unwrap_or_else(|_| "node".to_string());
-    let command = configured.trim();
-    if command.is_empty() {
-        return Err("CODEX_NODE_BIN cannot be empty".to_string());
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::oneshot::error::TryRecvError;
 
-    let command_path = Path::new(command);
-    if is_explicit_path(command_path) {
-        if !command_path.exists() {
-            return Err(format!(
-                "CODEX_NODE_BIN points to a missing file: {}",
-                command_path.to_string_lossy()
-            ));
-        }
-        if !command_path.is_file() {
-            return Err(format!("CODEX_NODE_BIN must point to a file: {command}"));
-        }
-        return Ok(command_path.to_path_buf());
+    #[test]
+    fn helper_events_are_redacted_before_they_reach_the_webview() {
+        let line = json!({
+            "type": "event",
+            "payload": {
+                "kind": "account/chatgptAuthTokens/refresh",
+                "params": { "accessToken": "opaque-token", "chatgptAccountId": "acct-1" },
+                "output": "export OPENAI_API_KEY=sk-proj-abcdefghijklmnopqrstuvwx",
+            },
+        });
+        let payload = helper_event_payload(&line).unwrap();
+        assert_eq!(payload["params"]["accessToken"], crate::redaction::REDACTED);
+        assert_eq!(payload["params"]["chatgptAccountId"], "acct-1");
+        assert_eq!(payload["output"], "export OPENAI_API_KEY=[REDACTED]");
+        assert_eq!(payload["kind"], "account/chatgptAuthTokens/refresh");
+        assert!(helper_event_payload(&json!({ "type": "event" })).is_none());
     }
 
-    if let Ok(canonical) = find_binary_in_path(command) {
-        return Ok(canonical);
-    }
+    #[tokio::test]
+    async fn old_helper_eof_after_a_respawn_leaves_the_new_start_pending() {
+        let runtime = BridgeRuntime::default();
+        let old_ready = runtime.begin_start_wait(1).await;
+        // Hot reload: generation 2 is spawned while generation 1's reader still drains.
+        let mut new_ready = runtime.begin_start_wait(2).await;
+        assert!(old_ready.await.is_err(), "the superseded wait fails");
 
-    Err(format!(
-        "Unable to find executable '{command}' in PATH. Set CODEX_NODE_BIN explicitly."
-    ))
-}
+        runtime.helper_output_closed(1).await;
+        assert!(matches!(new_ready.try_recv(), Err(TryRecvError::Empty)));
 
-fn is_explicit_path(path: &Path) -> bool {
-    if path.is_absolute() {
-        return true;
+        assert!(runtime.start_waiter.resolve(2, Ok(())).await);
+        assert!(matches!(new_ready.await, Ok(Ok(()))));
     }
-    let raw = path.to_string_lossy();
-    raw.contains(std::path::MAIN_SEPARATOR)
-        || raw.contains('/')
-        || raw.contains('\\')
-}
 
-fn find_binary_in_path(command: &str) -> Result<PathBuf, String> {
-    let path = std::env::var_os("PATH").ok_or_else(|| "PATH environment variable is not set".to_string())?;
+    #[tokio::test]
+    async fn helper_eof_fails_its_own_start_and_stop_waits() {
+        let runtime = BridgeRuntime::default();
+        let ready = runtime.begin_start_wait(3).await;
+        let ack = runtime.stop_waiter.begin(3).await;
 
-    for path_dir in std::env::split_paths(&path) {
-        let candidate = path_dir.join(command);
-        if candidate.exists() {
-            return Ok(candidate);
-        }
-
-        let path_with_exts: &[&str] = if cfg!(windows) {
-            &[".exe", ".cmd", ".bat"]
-        } else {
-            &[""]
-        };
-
-        for suffix in path_with_exts {
-            let candidate = path_dir.join(format!("{command}{suffix}"));
-            if candidate.exists() {
-                return Ok(candidate);
-            }
-        }
+        runtime.helper_output_closed(3).await;
+        let failure = ready.await.unwrap().unwrap_err();
+        assert!(failure.to_string().contains("helper exited before reporting ready"), "{failure}");
+        assert!(ack.await.is_err());
     }
 
-    Err(format!("command '{command}' not found in PATH"))
+    #[tokio::test]
+    async fn old_helper_eof_does_not_end_the_new_helpers_stop_ack_wait() {
+        let runtime = BridgeRuntime::default();
+        let mut ack = runtime.stop_waiter.begin(2).await;
+        runtime.helper_output_closed(1).await;
+        assert!(matches!(ack.try_recv(), Err(TryRecvError::Empty)));
+    }
 }
@@ -0,0 +1,238 @@
+// Command names are spread across generated Rust, hand-written handlers, build.rs
+// and the capability file; these tests fail when any of them drift apart.
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::bridge_contract_generated::{BRIDGE_COMMANDS, HELPER_COMMANDS};
+use crate::bridge_dispatch_generated::{helper_command_for_tauri_command, HELPER_FORWARD_TAURI_COMMANDS};
+use crate::host_commands::HOST_COMMANDS;
+use crate::host_events::HOST_EVENTS;
+
+const CAPABILITY: &str = include_str!("../capabilities/default.json");
+const MAIN_SOURCE: &str = include_str!("main.rs");
+const INVOKE_HANDLERS_SOURCE: &str = include_str!("bridge_invoke_handlers_generated.rs");
+const FORWARD_COMMANDS_SOURCE: &str = include_str!("bridge_commands_generated.rs");
+
+// Files that emit or match webview events; names there must come from host_events.rs.
+const EMITTING_SOURCES: &[(&str, &str)] = &[
+    ("main.rs", MAIN_SOURCE),
+    ("bridge_process.rs", include_str!("bridge_process.rs")),
+    ("diagnostics_bundle.rs", include_str!("diagnostics_bundle.rs")),
+    ("event_routing.rs", include_str!("event_routing.rs")),
+    ("instance_lock.rs", include_str!("instance_lock.rs")),
+    ("instance_socket.rs", include_str!("instance_socket.rs")),
+];
+
+// Helper commands the host sends on its own; no Tauri command maps to them.
+const HOST_ONLY_HELPER_COMMANDS: &[&str] = &["status"];
+
+fn unique<'a>(label: &str, names: impl IntoIterator<Item = &'a str>) -> BTreeSet<String> {
+    let mut seen = BTreeSet::new();
+    let duplicates = names
+        .into_iter()
+        .filter(|name| !seen.insert(name.to_string()))
+        .collect::<BTreeSet<_>>();
+    assert!(duplicates.is_empty(), "{label} lists {duplicates:?} more than once");
+    seen
+}
+
+fn assert_same_names(expected_label: &str, expected: &BTreeSet<String>, actual_label: &str, actual: &BTreeSet<String>) {
+    let missing = expected.difference(actual).collect::<Vec<_>>();
+    let extra = actual.difference(expected).collect::<Vec<_>>();
+    assert!(
+        missing.is_empty() && extra.is_empty(),
+        "{actual_label} does not match {expected_label}\n  missing from {actual_label}: {missing:?}\n  only in {actual_label}: {extra:?}"
+    );
+}
+
+fn manifest_commands() -> BTreeSet<String> {
+    unique("the app manifest", BRIDGE_COMMANDS.iter().chain(HOST_COMMANDS).copied())
+}
+
+fn capability_commands() -> BTreeSet<String> {
+    let capability: serde_json::Value = serde_json::from_str(CAPABILITY).expect("capabilities/default.json is not valid JSON");
+    let permissions = capability["permissions"]
+        .as_array()
+        .expect("capabilities/default.json has no permissions array");
+    let commands = permissions
+        .iter()
+        .filter_map(|permission| permission.as_str())
+        // Plugin and core permissions are namespaced; app command permissions are not.
+        .filter(|permission| !permission.contains(':'))
+        .map(|permission| {
+            permission
+                .strip_prefix("allow-")
+                .unwrap_or_else(|| panic!("capability permission \"{permission}\" is not an allow-<command> permission"))
+                .replace('-', "_")
+        })
+        .collect::<Vec<_>>();
+    unique("capabilities/default.json", commands.iter().map(String::as_str))
+}
+
+fn call_args<'a>(source: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let start = source
+        .find(open)
+        .unwrap_or_else(|| panic!("`{open}` not found"))
+        + open.len();
+    let len = source[start..]
+        .find(close)
+        .unwrap_or_else(|| panic!("`{open}` is not closed by `{close}`"));
+    source[start..start + len]
+        .split(',')
+        .map(str::trim)
+        .filter(|arg| !arg.is_empty())
+        .collect()
+}
+
+fn command_fns(source: &str) -> Vec<&str> {
+    let mut lines = source.lines().map(str::trim);
+    let mut names = Vec::new();
+    while let Some(line) = lines.next() {
+        if line != "#[tauri::command]" {
+            continue;
+        }
+        let signature = lines.next().unwrap_or_default();
+        let name = signature
+            .trim_start_matches("pub ")
+            .trim_start_matches("async ")
+            .strip_prefix("fn ")
+            .and_then(|rest| rest.split('(').next())
+            .unwrap_or_else(|| panic!("#[tauri::command] is not followed by a fn: {signature}"));
+        names.push(name);
+    }
+    names
+}
+
+#[test]
+fn capability_allows_exactly_the_manifest_commands() {
+    assert_same_names(
+        "BRIDGE_COMMANDS + HOST_COMMANDS",
+        &manifest_commands(),
+        "capabilities/default.json",
+        &capability_commands(),
+    );
+}
+
+// Detached per-thread windows are labelled `thread-<id>` and need the same commands.
+#[test]
+fn capability_covers_the_main_and_detached_thread_windows() {
+    let capability: serde_json::Value = serde_json::from_str(CAPABILITY).expect("capabilities/default.json is not valid JSON");
+    let windows = capability["windows"]
+        .as_array()
+        .expect("capabilities/default.json has no windows array")
+        .iter()
+        .map(|window| window.as_str().expect("window labels are strings"))
+        .collect::<Vec<_>>();
+    let covers = |label: &str| {
+        windows.iter().any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => label.starts_with(prefix),
+            None => *pattern == label,
+        })
+    };
+    assert!(covers("main"), "capabilities/default.json does not cover the main window");
+    assert!(covers("thread-019a2b"), "capabilities/default.json does not cover thread-* windows");
+    assert!(!covers("settings"), "capabilities/default.json covers windows other than main and thread-*");
+}
+
+#[test]
+fn every_manifest_command_has_a_permission_file() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("permissions/autogenerated");
+    // tauri-build writes these before the tests compile, and they are checked in.
+    let entries = std::fs::read_dir(&dir).unwrap_or_else(|e| panic!("cannot read {}: {e}", dir.display()));
+    let files = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|name| name.strip_suffix(".toml").map(str::to_string))
+        .collect::<BTreeSet<_>>();
+    assert_same_names(
+        "BRIDGE_COMMANDS + HOST_COMMANDS",
+        &manifest_commands(),
+        "permissions/autogenerated",
+        &files,
+    );
+}
+
+#[test]
+fn invoke_handler_registers_exactly_the_manifest_commands() {
+    let generated = call_args(INVOKE_HANDLERS_SOURCE, "tauri::generate_handler![", "$(");
+    assert_same_names(
+        "BRIDGE_COMMANDS",
+        &unique("BRIDGE_COMMANDS", BRIDGE_COMMANDS.iter().copied()),
+        "bridge_generate_handler!",
+        &unique("bridge_generate_handler!", generated.iter().copied()),
+    );
+
+    let host = call_args(MAIN_SOURCE, ".invoke_handler(bridge_generate_handler!(", ")");
+    assert_same_names(
+        "HOST_COMMANDS",
+        &unique("HOST_COMMANDS", HOST_COMMANDS.iter().copied()),
+        "the bridge_generate_handler! call in main.rs",
+        &unique("the bridge_generate_handler! call in main.rs", host.iter().copied()),
+    );
+}
+
+#[test]
+fn every_registered_command_is_defined_once() {
+    let defined = command_fns(MAIN_SOURCE)
+        .into_iter()
+        .chain(command_fns(FORWARD_COMMANDS_SOURCE))
+        .collect::<Vec<_>>();
+    assert_same_names(
+        "BRIDGE_COMMANDS + HOST_COMMANDS",
+        &manifest_commands(),
+        "#[tauri::command] fns",
+        &unique("#[tauri::command] fns", defined),
+    );
+}
+
+#[test]
+fn forwarded_commands_map_one_to_one_onto_helper_commands() {
+    let bridge = unique("BRIDGE_COMMANDS", BRIDGE_COMMANDS.iter().copied());
+    let helper = unique("HELPER_COMMANDS", HELPER_COMMANDS.iter().copied());
+    let forwarded = unique("HELPER_FORWARD_TAURI_COMMANDS", HELPER_FORWARD_TAURI_COMMANDS.iter().copied());
+    let not_in_bridge = forwarded.difference(&bridge).collect::<Vec<_>>();
+    assert!(
+        not_in_bridge.is_empty(),
+        "HELPER_FORWARD_TAURI_COMMANDS lists commands missing from BRIDGE_COMMANDS: {not_in_bridge:?}"
+    );
+
+    let mut targets = BTreeMap::<&str, Vec<&str>>::new();
+    for command in BRIDGE_COMMANDS {
+        match (forwarded.contains(*command), helper_command_for_tauri_command(command)) {
+            (true, Some(target)) => targets.entry(target).or_default().push(command),
+            (true, None) => panic!("{command} is forwarded but helper_command_for_tauri_command has no mapping for it"),
+            (false, Some(target)) => {
+                panic!("{command} maps to helper command {target} but is not in HELPER_FORWARD_TAURI_COMMANDS")
+            }
+            (false, None) => {}
+        }
+    }
+    let shared = targets
+        .iter()
+        .filter(|(_, commands)| commands.len() > 1)
+        .collect::<Vec<_>>();
+    assert!(shared.is_empty(), "helper commands reached from more than one Tauri command: {shared:?}");
+
+    let mapped = targets.keys().map(|target| target.to_string()).collect::<BTreeSet<_>>();
+    let expected = helper
+        .iter()
+        .filter(|command| !HOST_ONLY_HELPER_COMMANDS.contains(&command.as_str()))
+        .cloned()
+        .collect::<BTreeSet<_>>();
+    assert_same_names(
+        "HELPER_COMMANDS without host-only commands",
+        &expected,
+        "helper_command_for_tauri_command targets",
+        &mapped,
+    );
+}
+
+#[test]
+fn emitted_events_are_declared_once_in_host_events() {
+    unique("HOST_EVENTS", HOST_EVENTS.iter().map(|(event, _)| *event));
+    for (file, source) in EMITTING_SOURCES {
+        assert!(
+            !source.contains("\"codex:"),
+            "{file} names an event with a string literal; use the host_events.rs constants so the TypeScript bindings cover it"
+        );
+    }
+}
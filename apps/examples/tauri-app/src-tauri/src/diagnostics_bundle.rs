@@ -0,0 +1,248 @@
+#[cfg(feature = "diagnostics-export")]
+use flate2::{write::GzEncoder, Compression};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::helper_resolution;
+use crate::host_events;
+#[cfg(feature = "node-helper")]
+use crate::node_runtime;
+use crate::redaction::{redact_value, redacted, REDACTED};
+use crate::session_registry::AppBridgeState;
+
+const STATE_HISTORY_LIMIT: usize = 100;
+const LOG_LIMIT: usize = 500;
+const PROTOCOL_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsExport {
+    pub path: String,
+    #[cfg_attr(test, ts(as = "f64"))]
+    pub size_bytes: u64,
+    pub entries: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordedEvent {
+    ts_ms: u128,
+    event: String,
+    payload: Value,
+}
+
+#[derive(Default)]
+struct Recorded {
+    state_history: VecDeque<RecordedEvent>,
+    logs: VecDeque<RecordedEvent>,
+    protocol: VecDeque<RecordedEvent>,
+    session_configs: BTreeMap<String, Value>,
+}
+
+// Keeps bounded history of what every session emitted, across session restarts
+// and removals, so an export still covers a helper that has already gone away.
+#[derive(Default)]
+pub struct DiagnosticsRecorder {
+    recorded: Mutex<Recorded>,
+}
+
+impl DiagnosticsRecorder {
+    pub fn record_event(&self, event: &str, payload: &Value) {
+        if !cfg!(feature = "diagnostics-export") {
+            return;
+        }
+        let kind = payload.get("kind").and_then(Value::as_str);
+        let mut recorded = self.lock();
+        let (buffer, limit) = match (event, kind) {
+            (host_events::BRIDGE_STATE, _) => (&mut recorded.state_history, STATE_HISTORY_LIMIT),
+            (host_events::HELPER_EVENT, _) | (host_events::GLOBAL_MESSAGE, Some("protocol/raw_in")) => {
+                (&mut recorded.protocol, PROTOCOL_LIMIT)
+            }
+            (host_events::PROTOCOL_ERROR, _) | (host_events::GLOBAL_MESSAGE, _) => (&mut recorded.logs, LOG_LIMIT),
+            _ => return,
+        };
+        if buffer.len() == limit {
+            buffer.pop_front();
+        }
+        // Redacted again here so an export never depends on every emit site doing it.
+        buffer.push_back(RecordedEvent {
+            ts_ms: crate::now_unix_ms(),
+            event: event.to_string(),
+            payload: redacted(payload),
+        });
+    }
+
+    // Env override values are dropped outright: they are free-form and the key
+    // names alone say nothing about whether they hold secrets.
+    pub fn record_session_config(&self, session_id: &str, mut config: Value) {
+        if !cfg!(feature = "diagnostics-export") {
+            return;
+        }
+        if let Some(env) = config.pointer_mut("/helperProcess/env").and_then(Value::as_object_mut) {
+            for value in env.values_mut() {
+                *value = Value::String(REDACTED.to_string());
+            }
+        }
+        redact_value(&mut config);
+        self.lock().session_configs.insert(session_id.to_string(), config);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Recorded> {
+        self.recorded.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+// The archive always goes under the app data dir; the webview never picks where
+// the host writes.
+pub async fn export(app: &AppHandle, state: &AppBridgeState) -> Result<DiagnosticsExport, String> {
+    if !cfg!(feature = "diagnostics-export") {
+        return Err(crate::feature_disabled("diagnostics-export", "diagnostics export"));
+    }
+    let generated_at_ms = crate::now_unix_ms();
+    let path = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("failed to resolve app data dir for diagnostics: {e}"))?
+        .join("diagnostics")
+        .join(format!("codex-diagnostics-{generated_at_ms}.tar.gz"));
+
+    let current_state: BTreeMap<String, Value> = state
+        .snapshots()
+        .await
+        .into_iter()
+        .map(|(session_id, snapshot)| (session_id, json!(snapshot)))
+        .collect();
+    let trace = helper_resolution::describe_resolution(app);
+    let helper = describe_helper(&trace).await;
+    let (state_history, logs, protocol, session_configs) = {
+        let recorder = app.try_state::<DiagnosticsRecorder>();
+        let recorded = recorder.as_ref().map(|recorder| recorder.lock());
+        match recorded.as_deref() {
+            Some(recorded) => (
+                recorded.state_history.iter().cloned().collect::<Vec<_>>(),
+                recorded.logs.iter().cloned().collect::<Vec<_>>(),
+                recorded.protocol.iter().cloned().collect::<Vec<_>>(),
+                recorded.session_configs.clone(),
+            ),
+            None => Default::default(),
+        }
+    };
+
+    let manifest = json!({
+        "generatedAtMs": generated_at_ms,
+        "app": {
+            "name": app.package_info().name,
+            "version": app.package_info().version.to_string(),
+            "tauriVersion": tauri::VERSION,
+            "debugBuild": cfg!(debug_assertions),
+        },
+        "platform": { "os": std::env::consts::OS, "arch": std::env::consts::ARCH },
+        "helper": helper,
+        "sessionIds": current_state.keys().collect::<Vec<_>>(),
+    });
+
+    let files = [
+        ("manifest.json", pretty(manifest)?),
+        ("state/current.json", pretty(json!(current_state))?),
+        ("state/history.jsonl", json_lines(&state_history)?),
+        ("helper-resolution.json", pretty(json!(trace))?),
+        ("logs.jsonl", json_lines(&logs)?),
+        ("protocol.jsonl", json_lines(&protocol)?),
+        ("session-config.json", pretty(json!(session_configs))?),
+    ];
+    write_archive(&path, &files)?;
+
+    let size_bytes = std::fs::metadata(&path).map(|metadata| metadata.len()).unwrap_or(0);
+    Ok(DiagnosticsExport {
+        path: path.to_string_lossy().into_owned(),
+        size_bytes,
+        entries: files.iter().map(|(name, _)| name.to_string()).collect(),
+    })
+}
+
+async fn describe_helper(trace: &helper_resolution::ResolutionTrace) -> Value {
+    let script = trace.args.first().or(trace.command.as_ref()).map(PathBuf::from);
+    let sha256 = script
+        .as_ref()
+        .and_then(|script| std::fs::read(script).ok())
+        .map(|bytes| hex::encode(Sha256::digest(&bytes)));
+    #[cfg(not(feature = "node-helper"))]
+    let node_version: Option<Value> = None;
+    #[cfg(feature = "node-helper")]
+    let node_version = match (trace.mode.as_deref(), trace.command.clone()) {
+        (Some(mode), Some(command)) if mode.starts_with("node") => {
+            tokio::task::spawn_blocking(move || node_runtime::check_node(Path::new(&command)))
+                .await
+                .ok()
+                .map(|result| match result {
+                    Ok(version) => json!(version.to_string()),
+                    Err(error) => json!({ "error": error.to_string() }),
+                })
+        }
+        _ => None,
+    };
+    json!({
+        "mode": trace.mode,
+        "path": script,
+        "sha256": sha256,
+        "nodeVersion": node_version,
+    })
+}
+
+// Recorded events were redacted in record_event; this pass covers the current
+// snapshots and resolution trace.
+fn pretty(mut value: Value) -> Result<Vec<u8>, String> {
+    redact_value(&mut value);
+    serde_json::to_vec_pretty(&value).map_err(|e| format!("failed to encode diagnostics: {e}"))
+}
+
+fn json_lines(events: &[RecordedEvent]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    for event in events {
+        let mut value = json!(event);
+        redact_value(&mut value);
+        serde_json::to_writer(&mut out, &value).map_err(|e| format!("failed to encode diagnostics: {e}"))?;
+        out.push(b'\n');
+    }
+    Ok(out)
+}
+
+#[cfg(feature = "diagnostics-export")]
+fn write_archive(path: &Path, files: &[(&str, Vec<u8>)]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+    }
+    let file = std::fs::File::create(path).map_err(|e| format!("failed to create {}: {e}", path.display()))?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let mtime = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    for (name, contents) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        archive
+            .append_data(&mut header, name, contents.as_slice())
+            .map_err(|e| format!("failed to write {name} to diagnostics archive: {e}"))?;
+    }
+    archive
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .and_then(|file| file.sync_all())
+        .map_err(|e| format!("failed to finish diagnostics archive {}: {e}", path.display()))
+}
+
+// The archive format needs `tar` and `flate2`, which only the feature pulls in.
+#[cfg(not(feature = "diagnostics-export"))]
+fn write_archive(_path: &Path, _files: &[(&str, Vec<u8>)]) -> Result<(), String> {
+    Err(crate::feature_disabled("diagnostics-export", "diagnostics export"))
+}
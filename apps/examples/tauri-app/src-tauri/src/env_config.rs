@@ -0,0 +1,131 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+// Host settings from `CODEX_*` environment variables. An unset or blank variable
+// means "use the default"; any other value has to parse, so a typo is an error
+// instead of a silently ignored setting.
+
+const TRUE_VALUES: &[&str] = &["1", "true", "yes", "on"];
+const FALSE_VALUES: &[&str] = &["0", "false", "no", "off"];
+
+pub fn flag(name: &str) -> Result<Option<bool>, String> {
+    parse_flag(name, read(name).as_deref())
+}
+
+pub fn number<T>(name: &str) -> Result<Option<T>, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    parse_number(name, read(name).as_deref())
+}
+
+// Comma-separated names; blank entries are dropped.
+pub fn list(name: &str) -> Vec<String> {
+    parse_list(read(name).as_deref())
+}
+
+fn read(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+fn present(raw: Option<&str>) -> Option<&str> {
+    raw.map(str::trim).filter(|value| !value.is_empty())
+}
+
+fn parse_flag(name: &str, raw: Option<&str>) -> Result<Option<bool>, String> {
+    let Some(value) = present(raw) else {
+        return Ok(None);
+    };
+    let value = value.to_ascii_lowercase();
+    if TRUE_VALUES.contains(&value.as_str()) {
+        Ok(Some(true))
+    } else if FALSE_VALUES.contains(&value.as_str()) {
+        Ok(Some(false))
+    } else {
+        Err(format!(
+            "{name} must be one of {} or {}, got {value:?}",
+            TRUE_VALUES.join("/"),
+            FALSE_VALUES.join("/")
+        ))
+    }
+}
+
+fn parse_number<T>(name: &str, raw: Option<&str>) -> Result<Option<T>, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let Some(value) = present(raw) else {
+        return Ok(None);
+    };
+    value
+        .parse::<T>()
+        .map(Some)
+        .map_err(|e| format!("{name} must be {}, got {value:?}: {e}", number_kind::<T>()))
+}
+
+fn number_kind<T>() -> &'static str {
+    let type_name = std::any::type_name::<T>();
+    if type_name.starts_with('u') {
+        "a non-negative integer"
+    } else {
+        "an integer"
+    }
+}
+
+fn parse_list(raw: Option<&str>) -> Vec<String> {
+    present(raw)
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_accept_the_same_spellings_everywhere() {
+        for value in ["1", "true", "TRUE", " yes ", "on"] {
+            assert_eq!(parse_flag("CODEX_X", Some(value)), Ok(Some(true)), "{value:?}");
+        }
+        for value in ["0", "false", "No", "off"] {
+            assert_eq!(parse_flag("CODEX_X", Some(value)), Ok(Some(false)), "{value:?}");
+        }
+        assert_eq!(parse_flag("CODEX_X", None), Ok(None));
+        assert_eq!(parse_flag("CODEX_X", Some("  ")), Ok(None));
+    }
+
+    #[test]
+    fn unknown_flag_values_are_errors() {
+        let error = parse_flag("CODEX_X", Some("enabled")).unwrap_err();
+        assert_eq!(error, "CODEX_X must be one of 1/true/yes/on or 0/false/no/off, got \"enabled\"");
+    }
+
+    #[test]
+    fn numbers_parse_or_fail_with_the_variable_name() {
+        assert_eq!(parse_number::<u64>("CODEX_N", Some(" 250 ")), Ok(Some(250)));
+        assert_eq!(parse_number::<u64>("CODEX_N", None), Ok(None));
+        assert_eq!(parse_number::<u64>("CODEX_N", Some("")), Ok(None));
+        assert_eq!(parse_number::<i32>("CODEX_N", Some("-5")), Ok(Some(-5)));
+
+        let error = parse_number::<u64>("CODEX_N", Some("-1")).unwrap_err();
+        assert!(error.starts_with("CODEX_N must be a non-negative integer, got \"-1\""), "{error}");
+        let error = parse_number::<i32>("CODEX_N", Some("ten")).unwrap_err();
+        assert!(error.starts_with("CODEX_N must be an integer, got \"ten\""), "{error}");
+    }
+
+    #[test]
+    fn lists_split_on_commas_and_drop_blanks() {
+        assert_eq!(parse_list(Some(" A, ,B ,")), vec!["A".to_string(), "B".to_string()]);
+        assert!(parse_list(None).is_empty());
+        assert!(parse_list(Some("   ")).is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, EventTarget};
+
+use crate::host_events;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS), ts(optional_fields = nullable))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct EventSubscriptionConfig {
+    pub session_ids: Option<Vec<String>>,
+    pub thread_ids: Option<Vec<String>>,
+}
+
+// Empty sets match everything.
+#[derive(Debug, Default)]
+struct WindowSubscription {
+    session_ids: HashSet<String>,
+    thread_ids: HashSet<String>,
+}
+
+impl WindowSubscription {
+    fn matches(&self, session_id: &str, thread_id: Option<&str>) -> bool {
+        let session_ok = self.session_ids.is_empty() || self.session_ids.contains(session_id);
+        let thread_ok = match thread_id {
+            Some(thread_id) => self.thread_ids.is_empty() || self.thread_ids.contains(thread_id),
+            None => true,
+        };
+        session_ok && thread_ok
+    }
+}
+
+#[derive(Debug, Default)]
+struct Routes {
+    // Window label -> explicit subscription from `subscribe_events`.
+    subscriptions: HashMap<String, WindowSubscription>,
+    // Window label -> sessions that window started.
+    started: HashMap<String, HashSet<String>>,
+}
+
+impl Routes {
+    // A subscribed window gets what it asked for; any other window only gets the
+    // sessions it started itself, so one window never sees another's events by
+    // default.
+    fn delivers(&self, label: &str, session_id: &str, thread_id: Option<&str>) -> bool {
+        match self.subscriptions.get(label) {
+            Some(subscription) => subscription.matches(session_id, thread_id),
+            None => self.started.get(label).is_some_and(|sessions| sessions.contains(session_id)),
+        }
+    }
+}
+
+// Session events only reach listeners registered on a window
+// (`getCurrentWebviewWindow().listen`). Listeners from the global `listen` have
+// no window label to route by, so they receive none of them.
+#[derive(Default)]
+pub struct EventRouter {
+    routes: Mutex<Routes>,
+}
+
+impl EventRouter {
+    pub fn subscribe(&self, label: &str, config: EventSubscriptionConfig) {
+        let subscription = WindowSubscription {
+            session_ids: config.session_ids.unwrap_or_default().into_iter().collect(),
+            thread_ids: config.thread_ids.unwrap_or_default().into_iter().collect(),
+        };
+        self.lock().subscriptions.insert(label.to_string(), subscription);
+    }
+
+    pub fn unsubscribe(&self, label: &str) {
+        self.lock().subscriptions.remove(label);
+    }
+
+    pub fn record_start(&self, label: &str, session_id: &str) {
+        self.lock()
+            .started
+            .entry(label.to_string())
+            .or_default()
+            .insert(session_id.to_string());
+    }
+
+    pub fn forget_window(&self, label: &str) {
+        let mut routes = self.lock();
+        routes.subscriptions.remove(label);
+        routes.started.remove(label);
+    }
+
+    pub fn emit(&self, app: &AppHandle, event: &str, session_id: &str, payload: Value) -> tauri::Result<()> {
+        let routes = self.lock();
+        let thread_id = if event == host_events::HELPER_EVENT {
+            payload.get("threadId").and_then(|value| value.as_str()).map(str::to_string)
+        } else {
+            None
+        };
+        app.emit_filter(event, payload, |target| match target {
+            EventTarget::AnyLabel { label }
+            | EventTarget::Window { label }
+            | EventTarget::Webview { label }
+            | EventTarget::WebviewWindow { label } => routes.delivers(label, session_id, thread_id.as_deref()),
+            _ => false,
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Routes> {
+        self.routes.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_only_receive_sessions_they_started_unless_subscribed() {
+        let router = EventRouter::default();
+        router.record_start("main", "repo-a");
+        let routes = router.lock();
+        assert!(routes.delivers("main", "repo-a", Some("thread-1")));
+        assert!(!routes.delivers("main", "repo-b", None));
+        assert!(!routes.delivers("detached", "repo-a", None));
+    }
+
+    #[test]
+    fn subscriptions_replace_the_default_route() {
+        let router = EventRouter::default();
+        router.record_start("main", "repo-a");
+        router.subscribe(
+            "main",
+            EventSubscriptionConfig {
+                session_ids: Some(vec!["repo-b".to_string()]),
+                thread_ids: Some(vec!["thread-1".to_string()]),
+            },
+        );
+        {
+            let routes = router.lock();
+            assert!(routes.delivers("main", "repo-b", Some("thread-1")));
+            assert!(!routes.delivers("main", "repo-b", Some("thread-2")));
+            assert!(!routes.delivers("main", "repo-a", None));
+        }
+
+        router.unsubscribe("main");
+        assert!(router.lock().delivers("main", "repo-a", None));
+        router.forget_window("main");
+        assert!(!router.lock().delivers("main", "repo-a", None));
+    }
+}
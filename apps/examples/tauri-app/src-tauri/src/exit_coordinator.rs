@@ -0,0 +1,75 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use tauri::{AppHandle, ExitRequestApi, Manager};
+
+use crate::host_config::HostConfig;
+use crate::helper_shutdown::ShutdownConfig;
+use crate::session_registry::AppBridgeState;
+
+const IDLE: u8 = 0;
+const STOPPING: u8 = 1;
+const STOPPED: u8 = 2;
+
+// Closing a window never stops the helper on its own. Tauri requests exit once the
+// last window is gone (`code: None`) or when the app quits explicitly (`code: Some`);
+// the first request holds the exit, stops the helper once in the background, and
+// re-issues the exit when shutdown has finished.
+pub struct ExitCoordinator {
+    state: AtomicU8,
+    keep_running_in_background: bool,
+}
+
+impl ExitCoordinator {
+    // Background mode needs the tray: without it a closed last window would leave a
+    // process with no way back to the UI and no way to quit.
+    pub fn new(config: &HostConfig) -> Result<Self, String> {
+        if config.keep_running_in_background && !cfg!(feature = "tray") {
+            return Err(crate::feature_disabled("tray", "keepRunningInBackground"));
+        }
+        Ok(Self {
+            state: AtomicU8::new(IDLE),
+            keep_running_in_background: config.keep_running_in_background,
+        })
+    }
+
+
+    pub fn on_exit_requested(&self, app: &AppHandle, api: &ExitRequestApi, code: Option<i32>) {
+        // Last window closed: keep the helper alive until an explicit quit.
+        if code.is_none() && self.keep_running_in_background {
+            api.prevent_exit();
+            return;
+        }
+        match self
+            .state
+            .compare_exchange(IDLE, STOPPING, Ordering::SeqCst, Ordering::SeqCst)
+        {
+            Ok(_) => {
+                api.prevent_exit();
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    stop_helper(&app).await;
+                    app.state::<ExitCoordinator>().state.store(STOPPED, Ordering::SeqCst);
+                    app.exit(code.unwrap_or(0));
+                });
+            }
+            Err(STOPPING) => api.prevent_exit(),
+            Err(_) => {}
+        }
+    }
+
+    // Last resort when the process exits without a prior exit request.
+    pub fn on_exit(&self, app: &AppHandle) {
+        if self
+            .state
+            .compare_exchange(IDLE, STOPPING, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+        {
+            tauri::async_runtime::block_on(stop_helper(app));
+            self.state.store(STOPPED, Ordering::SeqCst);
+        }
+    }
+}
+
+async fn stop_helper(app: &AppHandle) {
+    let shutdown = ShutdownConfig::resolve(None).unwrap_or_default();
+    app.state::<AppBridgeState>().stop_all(app, shutdown).await;
+}
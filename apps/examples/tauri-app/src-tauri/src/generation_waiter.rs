@@ -0,0 +1,75 @@
+use tokio::sync::{oneshot, Mutex};
+
+// A pending wait that only the helper process of one generation may answer.
+// Reader tasks of a replaced helper keep draining its pipes after a respawn, so
+// their EOF or a late ack must not resolve a wait that belongs to its successor.
+pub struct GenerationWaiter<T> {
+    slot: Mutex<Option<(u64, oneshot::Sender<T>)>>,
+}
+
+impl<T> Default for GenerationWaiter<T> {
+    fn default() -> Self {
+        Self { slot: Mutex::new(None) }
+    }
+}
+
+impl<T> GenerationWaiter<T> {
+    // Replacing a pending sender drops it, which fails the superseded wait.
+    pub async fn begin(&self, generation: u64) -> oneshot::Receiver<T> {
+        let (sender, receiver) = oneshot::channel();
+        *self.slot.lock().await = Some((generation, sender));
+        receiver
+    }
+
+    // Returns whether a wait for `generation` was pending.
+    pub async fn resolve(&self, generation: u64, value: T) -> bool {
+        match self.take(generation).await {
+            Some(sender) => {
+                let _ = sender.send(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Dropping the sender ends the wait with a receive error.
+    pub async fn cancel(&self, generation: u64) {
+        self.take(generation).await;
+    }
+
+    async fn take(&self, generation: u64) -> Option<oneshot::Sender<T>> {
+        let mut slot = self.slot.lock().await;
+        match slot.as_ref() {
+            Some((pending, _)) if *pending == generation => slot.take().map(|(_, sender)| sender),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oneshot::error::TryRecvError;
+
+    #[tokio::test]
+    async fn only_the_matching_generation_resolves_a_wait() {
+        let waiter = GenerationWaiter::<u32>::default();
+        let mut receiver = waiter.begin(2).await;
+
+        assert!(!waiter.resolve(1, 10).await);
+        waiter.cancel(1).await;
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+        assert!(waiter.resolve(2, 20).await);
+        assert_eq!(receiver.await, Ok(20));
+        assert!(!waiter.resolve(2, 30).await);
+    }
+
+    #[tokio::test]
+    async fn a_new_wait_fails_the_one_it_replaces() {
+        let waiter = GenerationWaiter::<u32>::default();
+        let first = waiter.begin(1).await;
+        let _second = waiter.begin(1).await;
+        assert!(first.await.is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use tokio::time::Duration;
+
+use crate::env_config;
+
+const DEFAULT_INTERVAL_MS: u64 = 10_000;
+const DEFAULT_TIMEOUT_MS: u64 = 5_000;
+const DEFAULT_MAX_MISSES: u32 = 3;
+
+pub const UNRESPONSIVE_ERROR_CODE: &str = "helper_unresponsive";
+
+#[derive(Debug, Clone, Copy)]
+pub struct HeartbeatConfig {
+    pub interval: Duration,
+    pub timeout: Duration,
+    pub max_misses: u32,
+    pub restart: bool,
+}
+
+impl HeartbeatConfig {
+    // Returns None when heartbeats are disabled (`CODEX_HELPER_HEARTBEAT_INTERVAL_MS=0`).
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let interval_ms = env_config::number("CODEX_HELPER_HEARTBEAT_INTERVAL_MS")?.unwrap_or(DEFAULT_INTERVAL_MS);
+        if interval_ms == 0 {
+            return Ok(None);
+        }
+        let timeout_ms = env_config::number("CODEX_HELPER_HEARTBEAT_TIMEOUT_MS")?.unwrap_or(DEFAULT_TIMEOUT_MS);
+        let max_misses = env_config::number("CODEX_HELPER_HEARTBEAT_MAX_MISSES")?.unwrap_or(DEFAULT_MAX_MISSES);
+        let restart = env_config::flag("CODEX_HELPER_RESTART_ON_UNRESPONSIVE")?.unwrap_or(false);
+        Ok(Some(Self {
+            interval: Duration::from_millis(interval_ms),
+            timeout: Duration::from_millis(timeout_ms.max(1)),
+            max_misses: max_misses.max(1),
+            restart,
+        }))
+    }
+}
@@ -0,0 +1,39 @@
+use std::path::Path;
+use std::time::SystemTime;
+use tokio::time::Duration;
+
+use crate::env_config;
+
+const DEFAULT_INTERVAL_MS: u64 = 1_000;
+
+#[derive(Debug, Clone, Copy)]
+pub struct HotReloadConfig {
+    pub interval: Duration,
+}
+
+impl HotReloadConfig {
+    // Debug builds only; `CODEX_HELPER_HOT_RELOAD=0` turns it off there too.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        if !cfg!(debug_assertions) || !env_config::flag("CODEX_HELPER_HOT_RELOAD")?.unwrap_or(true) {
+            return Ok(None);
+        }
+        let interval_ms = env_config::number("CODEX_HELPER_HOT_RELOAD_INTERVAL_MS")?.unwrap_or(DEFAULT_INTERVAL_MS);
+        Ok(Some(Self {
+            interval: Duration::from_millis(interval_ms.max(1)),
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fingerprint {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+pub fn fingerprint(path: &Path) -> Option<Fingerprint> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some(Fingerprint {
+        modified: metadata.modified().ok(),
+        len: metadata.len(),
+    })
+}
@@ -0,0 +1,57 @@
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+// `name=sha256hex` pairs recorded by build.rs from `dist-node` at compile time.
+// Pins are keyed by file name only: the name picks which digest to compare, and
+// the bytes still have to match it, wherever the helper was resolved from.
+const PINNED_DIGESTS: Option<&str> = option_env!("CODEX_HELPER_PINS");
+
+const INTEGRITY_ERROR_CODE: &str = "E_TAURI_HELPER_INTEGRITY";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrityStatus {
+    // Debug builds run whatever was just built.
+    NotEnforced,
+    Verified,
+    // `allowUnverifiedHelper` is set in the app config; the caller reports it.
+    Unverified,
+}
+
+fn pinned_digest(file_name: &str) -> Option<&'static str> {
+    PINNED_DIGESTS?
+        .split(',')
+        .filter_map(|pin| pin.split_once('='))
+        .find(|(name, _)| *name == file_name)
+        .map(|(_, digest)| digest)
+}
+
+// Release builds only spawn a helper whose bytes match the digest pinned for its
+// file name. The bypass comes from the host config compiled into the app, never
+// from the environment the app was launched with.
+// The check hashes the file and the caller then spawns it by path, so anyone who
+// can write to the helper's directory can swap it in between. It guards against
+// a stale or corrupted bundle, not against a local attacker with that access.
+pub fn verify_helper(path: &Path, allow_unverified: bool) -> Result<IntegrityStatus, String> {
+    if cfg!(debug_assertions) {
+        return Ok(IntegrityStatus::NotEnforced);
+    }
+    if allow_unverified {
+        return Ok(IntegrityStatus::Unverified);
+    }
+    let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let Some(expected) = pinned_digest(&file_name) else {
+        return Err(format!(
+            "[{INTEGRITY_ERROR_CODE}] no digest was pinned for helper \"{file_name}\" at build time ({}). Rebuild with the helper in `dist-node`.",
+            path.display()
+        ));
+    };
+    let bytes = std::fs::read(path).map_err(|e| format!("failed to read helper {} for integrity check: {e}", path.display()))?;
+    let actual = hex::encode(Sha256::digest(&bytes));
+    if actual != expected {
+        return Err(format!(
+            "[{INTEGRITY_ERROR_CODE}] helper {} does not match the digest pinned at build time (expected sha256 {expected}, found {actual}).",
+            path.display()
+        ));
+    }
+    Ok(IntegrityStatus::Verified)
+}
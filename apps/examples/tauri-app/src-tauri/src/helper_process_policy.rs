@@ -0,0 +1,368 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use tokio::process::Command;
+
+use crate::env_config;
+
+// Variables the helper, Node and `codex app-server` need to run; everything
+// else in the host environment is dropped. A trailing `*` matches a prefix.
+const DEFAULT_ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LOGNAME",
+    "SHELL",
+    "LANG",
+    "LC_*",
+    "TZ",
+    "TERM",
+    "TMPDIR",
+    "XDG_*",
+    "CODEX_HOME",
+    "CODEX_BIN",
+    "CODEX_BRIDGE_RAW_LOG",
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "NO_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "no_proxy",
+    "SSL_CERT_FILE",
+    "SSL_CERT_DIR",
+    "NODE_EXTRA_CA_CERTS",
+    // Windows process essentials.
+    "SYSTEMROOT",
+    "WINDIR",
+    "COMSPEC",
+    "PATHEXT",
+    "TEMP",
+    "TMP",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "PROGRAMDATA",
+    "HOMEDRIVE",
+    "HOMEPATH",
+];
+
+// Session overrides come from the webview. These names choose which executable
+// runs, load code into it, or redirect its trust and network settings, so only
+// the host environment may set them.
+const SESSION_ENV_DENYLIST: &[&str] = &[
+    // Executable lookup and shell startup.
+    "PATH",
+    "PATHEXT",
+    "COMSPEC",
+    "SHELL",
+    "BASH_ENV",
+    "ENV",
+    "ZDOTDIR",
+    "IFS",
+    // Host and helper settings, including CODEX_BIN and CODEX_HOME.
+    "CODEX_*",
+    // Dynamic loaders.
+    "LD_*",
+    "DYLD_*",
+    // Language runtimes the helper or Codex tools start.
+    "NODE_*",
+    "NPM_CONFIG_*",
+    "npm_config_*",
+    "PYTHON*",
+    "PERL5*",
+    "PERLLIB",
+    "RUBYOPT",
+    "RUBYLIB",
+    "GIT_*",
+    // TLS trust and proxies.
+    "SSL_CERT_FILE",
+    "SSL_CERT_DIR",
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "NO_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "all_proxy",
+    "no_proxy",
+    // Config and home lookup.
+    "HOME",
+    "USERPROFILE",
+    "XDG_CONFIG_HOME",
+    "XDG_DATA_HOME",
+    "SYSTEMROOT",
+    "WINDIR",
+];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS), ts(optional_fields = nullable))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HelperProcessConfig {
+    pub env: Option<BTreeMap<String, String>>,
+    #[cfg_attr(test, ts(as = "Option<f64>"))]
+    pub max_address_space_mb: Option<u64>,
+    #[cfg_attr(test, ts(as = "Option<f64>"))]
+    pub max_open_files: Option<u64>,
+    pub nice: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    pub max_address_space_bytes: Option<u64>,
+    pub max_open_files: Option<u64>,
+    pub nice: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HelperProcessPolicy {
+    pub env_allow: Vec<String>,
+    pub env: BTreeMap<String, String>,
+    pub limits: ResourceLimits,
+}
+
+impl HelperProcessPolicy {
+    // The allowlist is host-only (`CODEX_HELPER_ENV_ALLOW`); a session may add
+    // overrides outside the denylist, and can only tighten resource limits.
+    pub fn resolve(session: Option<&HelperProcessConfig>) -> Result<Self, String> {
+        let mut env_allow: Vec<String> = DEFAULT_ENV_ALLOWLIST.iter().map(|name| name.to_string()).collect();
+        env_allow.extend(env_config::list("CODEX_HELPER_ENV_ALLOW"));
+
+        let host_limits = ResourceLimits {
+            max_address_space_bytes: env_config::number("CODEX_HELPER_MAX_ADDRESS_SPACE_MB")?.map(megabytes),
+            max_open_files: env_config::number("CODEX_HELPER_MAX_OPEN_FILES")?,
+            nice: env_config::number::<i32>("CODEX_HELPER_NICE")?
+                .map(|nice| check_nice("CODEX_HELPER_NICE", nice))
+                .transpose()?,
+        };
+
+        let mut policy = Self {
+            env_allow,
+            env: BTreeMap::new(),
+            limits: host_limits,
+        };
+        let Some(session) = session else {
+            return Ok(policy);
+        };
+
+        if let Some(overrides) = &session.env {
+            for (name, value) in overrides {
+                validate_session_env_name(name)?;
+                policy.env.insert(name.clone(), value.clone());
+            }
+        }
+        policy.limits.max_address_space_bytes = tighter(
+            host_limits.max_address_space_bytes,
+            session.max_address_space_mb.map(megabytes),
+        );
+        policy.limits.max_open_files = tighter(host_limits.max_open_files, session.max_open_files);
+        let session_nice = session.nice.map(check_session_nice).transpose()?;
+        policy.limits.nice = match (host_limits.nice, session_nice) {
+            (Some(host), Some(value)) => Some(host.max(value)),
+            (host, value) => host.or(value),
+        };
+        Ok(policy)
+    }
+
+    pub fn environment(&self) -> Vec<(OsString, OsString)> {
+        self.filter_environment(std::env::vars_os())
+    }
+
+    fn filter_environment(&self, host: impl Iterator<Item = (OsString, OsString)>) -> Vec<(OsString, OsString)> {
+        let mut environment: Vec<(OsString, OsString)> = host
+            .filter(|(name, _)| {
+                name.to_str()
+                    .is_some_and(|name| self.env_allow.iter().any(|pattern| env_name_matches(pattern, name)))
+            })
+            .filter(|(name, _)| !name.to_str().is_some_and(|name| self.env.contains_key(name)))
+            .collect();
+        environment.extend(
+            self.env
+                .iter()
+                .map(|(name, value)| (OsString::from(name), OsString::from(value))),
+        );
+        environment
+    }
+
+    pub fn apply(&self, command: &mut Command) {
+        command.env_clear();
+        command.envs(self.environment());
+        apply_resource_limits(command, self.limits);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn apply_resource_limits(command: &mut Command, limits: ResourceLimits) {
+    if limits == ResourceLimits::default() {
+        return;
+    }
+    // SAFETY: the closure runs in the forked child before exec and only calls
+    // async-signal-safe libc functions.
+    unsafe {
+        command.pre_exec(move || {
+            let set_rlimit = |resource, value: u64| {
+                let limit = libc::rlimit {
+                    rlim_cur: value,
+                    rlim_max: value,
+                };
+                if libc::setrlimit(resource, &limit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            };
+            if let Some(bytes) = limits.max_address_space_bytes {
+                set_rlimit(libc::RLIMIT_AS, bytes)?;
+            }
+            if let Some(files) = limits.max_open_files {
+                set_rlimit(libc::RLIMIT_NOFILE, files)?;
+            }
+            if let Some(nice) = limits.nice {
+                if libc::setpriority(libc::PRIO_PROCESS, 0, nice) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+// Resource limits are only enforced on Linux; elsewhere only the env policy applies.
+#[cfg(not(target_os = "linux"))]
+fn apply_resource_limits(_command: &mut Command, _limits: ResourceLimits) {}
+
+fn env_name_matches(pattern: &str, name: &str) -> bool {
+    let eq = |a: &str, b: &str| {
+        if cfg!(windows) {
+            a.eq_ignore_ascii_case(b)
+        } else {
+            a == b
+        }
+    };
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.get(..prefix.len()).is_some_and(|head| eq(head, prefix)),
+        None => eq(pattern, name),
+    }
+}
+
+fn validate_session_env_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.contains('=') || name.contains('\0') || name.contains('*') {
+        return Err(format!("helperProcess env name is invalid: {name:?}"));
+    }
+    if SESSION_ENV_DENYLIST.iter().any(|pattern| env_name_matches(pattern, name)) {
+        return Err(format!("helperProcess env may not set {name}"));
+    }
+    Ok(())
+}
+
+fn tighter(host: Option<u64>, session: Option<u64>) -> Option<u64> {
+    match (host, session) {
+        (Some(host), Some(session)) => Some(host.min(session)),
+        (host, session) => host.or(session),
+    }
+}
+
+fn megabytes(value: u64) -> u64 {
+    value.saturating_mul(1024 * 1024)
+}
+
+fn check_nice(name: &str, nice: i32) -> Result<i32, String> {
+    if !(-20..=19).contains(&nice) {
+        return Err(format!("{name} must be between -20 and 19, got {nice}"));
+    }
+    Ok(nice)
+}
+
+// A negative nice raises the helper above the host, which a session may not ask for.
+fn check_session_nice(nice: i32) -> Result<i32, String> {
+    let nice = check_nice("helperProcess.nice", nice)?;
+    if nice < 0 {
+        return Err(format!(
+            "helperProcess.nice must be between 0 and 19, got {nice}; sessions can only lower the helper's priority"
+        ));
+    }
+    Ok(nice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_env(name: &str) -> HelperProcessConfig {
+        HelperProcessConfig {
+            env: Some(BTreeMap::from([(name.to_string(), "value".to_string())])),
+            ..Default::default()
+        }
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Iterator<Item = (OsString, OsString)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (OsString::from(name), OsString::from(value)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn sessions_cannot_override_executable_loader_or_runtime_variables() {
+        for name in [
+            "PATH",
+            "CODEX_BIN",
+            "CODEX_HOME",
+            "BASH_ENV",
+            "NODE_OPTIONS",
+            "NODE_EXTRA_CA_CERTS",
+            "LD_PRELOAD",
+            "DYLD_INSERT_LIBRARIES",
+            "PYTHONPATH",
+            "GIT_SSH_COMMAND",
+            "HTTPS_PROXY",
+            "HOME",
+        ] {
+            let error = HelperProcessPolicy::resolve(Some(&session_env(name))).unwrap_err();
+            assert_eq!(error, format!("helperProcess env may not set {name}"));
+        }
+    }
+
+    #[test]
+    fn sessions_can_set_other_variables() {
+        let policy = HelperProcessPolicy::resolve(Some(&session_env("CONVEX_DEPLOYMENT"))).unwrap();
+        assert_eq!(policy.env.get("CONVEX_DEPLOYMENT").map(String::as_str), Some("value"));
+    }
+
+    #[test]
+    fn sessions_cannot_raise_the_helper_priority() {
+        let config = |nice| HelperProcessConfig {
+            nice: Some(nice),
+            ..Default::default()
+        };
+        let error = HelperProcessPolicy::resolve(Some(&config(-5))).unwrap_err();
+        assert!(error.starts_with("helperProcess.nice must be between 0 and 19, got -5"), "{error}");
+        assert!(HelperProcessPolicy::resolve(Some(&config(5))).is_ok());
+    }
+
+    #[test]
+    fn session_allowlist_extensions_are_not_accepted() {
+        let error = serde_json::from_value::<HelperProcessConfig>(serde_json::json!({ "envAllow": ["AWS_SECRET_ACCESS_KEY"] }))
+            .unwrap_err();
+        assert!(error.to_string().contains("unknown field `envAllow`"), "{error}");
+    }
+
+    #[test]
+    fn environment_keeps_only_allowlisted_host_variables_and_session_overrides() {
+        let policy = HelperProcessPolicy {
+            env_allow: vec!["PATH".to_string(), "LC_*".to_string()],
+            env: BTreeMap::from([("CONVEX_DEPLOYMENT".to_string(), "dev".to_string())]),
+            limits: ResourceLimits::default(),
+        };
+        let mut environment = policy.filter_environment(vars(&[
+            ("PATH", "/usr/bin"),
+            ("LC_ALL", "C"),
+            ("AWS_SECRET_ACCESS_KEY", "secret"),
+            ("CONVEX_DEPLOYMENT", "host"),
+        ]));
+        environment.sort();
+        assert_eq!(
+            environment,
+            vars(&[("CONVEX_DEPLOYMENT", "dev"), ("LC_ALL", "C"), ("PATH", "/usr/bin")]).collect::<Vec<_>>()
+        );
+    }
+}
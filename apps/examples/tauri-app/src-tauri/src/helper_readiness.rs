@@ -0,0 +1,84 @@
+use serde::Serialize;
+use std::fmt;
+use tokio::time::Duration;
+
+use crate::env_config;
+
+const DEFAULT_READY_TIMEOUT_MS: u64 = 60_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum StartStage {
+    #[default]
+    Spawn,
+    Handshake,
+    ConvexConnect,
+    AppServerInit,
+}
+
+impl StartStage {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "spawn" => Some(StartStage::Spawn),
+            "handshake" => Some(StartStage::Handshake),
+            "convex_connect" => Some(StartStage::ConvexConnect),
+            "app_server_init" => Some(StartStage::AppServerInit),
+            _ => None,
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            StartStage::Spawn => "E_TAURI_START_SPAWN",
+            StartStage::Handshake => "E_TAURI_START_HANDSHAKE",
+            StartStage::ConvexConnect => "E_TAURI_START_CONVEX_CONNECT",
+            StartStage::AppServerInit => "E_TAURI_START_APP_SERVER_INIT",
+        }
+    }
+}
+
+pub type StartOutcome = Result<(), StartFailure>;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartFailure {
+    pub stage: StartStage,
+    pub message: String,
+    pub timed_out: bool,
+}
+
+impl StartFailure {
+    pub fn new(stage: StartStage, message: impl Into<String>) -> Self {
+        Self {
+            stage,
+            message: message.into(),
+            timed_out: false,
+        }
+    }
+
+    pub fn timed_out(stage: StartStage, after: Duration) -> Self {
+        Self {
+            stage,
+            message: format!("helper did not report ready within {}ms", after.as_millis()),
+            timed_out: true,
+        }
+    }
+}
+
+impl fmt::Display for StartFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.stage.code(), self.message)
+    }
+}
+
+pub fn ready_timeout(override_ms: Option<u64>) -> Result<Duration, String> {
+    let ms = match override_ms {
+        Some(ms) => ms,
+        None => env_config::number("CODEX_HELPER_START_TIMEOUT_MS")?.unwrap_or(DEFAULT_READY_TIMEOUT_MS),
+    };
+    if ms == 0 {
+        return Err("helper start timeout must be greater than 0ms".to_string());
+    }
+    Ok(Duration::from_millis(ms))
+}
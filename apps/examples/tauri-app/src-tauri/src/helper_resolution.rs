@@ -0,0 +1,370 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+const PLUGIN_CONFIG_KEY: &str = "codexHelper";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CandidateSource {
+    // `value` names an environment variable holding the path.
+    Env,
+    // `value` is a bundled resource name.
+    Resource,
+    // `value` is a path relative to the current directory.
+    Path,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CandidateRuntime {
+    #[default]
+    Binary,
+    Node,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HelperCandidate {
+    pub source: CandidateSource,
+    pub value: String,
+    #[serde(default)]
+    pub runtime: CandidateRuntime,
+    #[serde(default)]
+    pub debug_only: bool,
+}
+
+impl HelperCandidate {
+    fn new(source: CandidateSource, value: &str, runtime: CandidateRuntime) -> Self {
+        Self {
+            source,
+            value: value.to_string(),
+            runtime,
+            debug_only: false,
+        }
+    }
+
+    fn mode(&self) -> &'static str {
+        match (self.runtime, self.source) {
+            (CandidateRuntime::Node, CandidateSource::Path) if self.debug_only => "node-js-dev-local",
+            (CandidateRuntime::Node, _) => "node-js",
+            (CandidateRuntime::Binary, _) => "standalone-binary",
+        }
+    }
+
+    fn describe(&self) -> String {
+        let source = match self.source {
+            CandidateSource::Env => "env",
+            CandidateSource::Resource => "resource",
+            CandidateSource::Path => "path",
+        };
+        let runtime = match self.runtime {
+            CandidateRuntime::Binary => "binary",
+            CandidateRuntime::Node => "node",
+        };
+        format!("{source}:{} ({runtime})", self.value)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HelperResolutionConfig {
+    pub candidates: Option<Vec<HelperCandidate>>,
+}
+
+// Ordered candidate list, from `plugins.codexHelper` in tauri.conf.json or set
+// on the builder with `.manage(HelperResolver::new(...))`.
+#[derive(Debug, Clone)]
+pub struct HelperResolver {
+    candidates: Vec<HelperCandidate>,
+}
+
+impl Default for HelperResolver {
+    fn default() -> Self {
+        use CandidateRuntime::{Binary, Node};
+        use CandidateSource::{Env, Path, Resource};
+        // In tauri dev, the resource directory can hold stale copies from older runs,
+        // so the freshly built local JS helper wins in debug builds.
+        let dev_script = HelperCandidate {
+            debug_only: true,
+            ..HelperCandidate::new(Path, "../dist-node/bridge-helper.js", Node)
+        };
+        let candidates = vec![
+            HelperCandidate::new(Env, "CODEX_HELPER_BIN", Binary),
+            dev_script,
+            HelperCandidate::new(Resource, "bridge-helper", Binary),
+            HelperCandidate::new(Resource, "bridge-helper.exe", Binary),
+            HelperCandidate::new(Resource, "bridge-helper.js", Node),
+            HelperCandidate::new(Path, "../dist-node/bridge-helper", Binary),
+            HelperCandidate::new(Path, "../dist-node/bridge-helper.exe", Binary),
+            HelperCandidate::new(Path, "../dist-node/bridge-helper.js", Node),
+        ];
+        // Builds without Node.js support only look for standalone helpers.
+        Self::new(
+            candidates
+                .into_iter()
+                .filter(|candidate| cfg!(feature = "node-helper") || candidate.runtime == Binary)
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateStatus {
+    Selected,
+    Rejected,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct CandidateOutcome {
+    pub candidate: String,
+    pub path: Option<String>,
+    pub status: CandidateStatus,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionTrace {
+    pub candidates: Vec<CandidateOutcome>,
+    pub mode: Option<String>,
+    pub command: Option<String>,
+    pub args: Vec<String>,
+}
+
+impl ResolutionTrace {
+    fn record(&mut self, candidate: &HelperCandidate, path: Option<&Path>, status: CandidateStatus, reason: Option<String>) {
+        self.candidates.push(CandidateOutcome {
+            candidate: candidate.describe(),
+            path: path.map(|path| path.to_string_lossy().into_owned()),
+            status,
+            reason,
+        });
+    }
+
+    fn summary(&self) -> String {
+        self.candidates
+            .iter()
+            .map(|outcome| {
+                let path = outcome.path.as_deref().map(|path| format!(" [{path}]")).unwrap_or_default();
+                let reason = outcome.reason.as_deref().unwrap_or("skipped");
+                format!("{}{path}: {reason}", outcome.candidate)
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+pub struct ResolvedHelper {
+    pub command: PathBuf,
+    pub args: Vec<String>,
+    pub mode: &'static str,
+    pub runtime: CandidateRuntime,
+}
+
+impl HelperResolver {
+    pub fn new(candidates: Vec<HelperCandidate>) -> Self {
+        Self { candidates }
+    }
+
+    pub fn from_app_config(app: &AppHandle) -> Result<Self, String> {
+        let Some(value) = app.config().plugins.0.get(PLUGIN_CONFIG_KEY) else {
+            return Ok(Self::default());
+        };
+        let config: HelperResolutionConfig = serde_json::from_value(value.clone())
+            .map_err(|e| format!("invalid plugins.{PLUGIN_CONFIG_KEY} config: {e}"))?;
+        Ok(match config.candidates {
+            Some(candidates) => Self::new(candidates),
+            None => Self::default(),
+        })
+    }
+
+    // Walks the candidates in order. The trace lists every candidate up to the
+    // winner; on failure it is part of the error message.
+    pub fn resolve(&self, app: &AppHandle) -> (Result<ResolvedHelper, String>, ResolutionTrace) {
+        let mut trace = ResolutionTrace::default();
+        for candidate in &self.candidates {
+            if candidate.debug_only && !cfg!(debug_assertions) {
+                trace.record(candidate, None, CandidateStatus::Skipped, Some("debug builds only".to_string()));
+                continue;
+            }
+            let path = match locate(app, candidate) {
+                Ok(Some(path)) => path,
+                Ok(None) => {
+                    trace.record(candidate, None, CandidateStatus::Skipped, Some("not configured".to_string()));
+                    continue;
+                }
+                Err(reason) => {
+                    trace.record(candidate, None, CandidateStatus::Rejected, Some(reason.clone()));
+                    // An explicitly configured env path that is wrong must not silently fall through.
+                    if candidate.source == CandidateSource::Env {
+                        let error = format!("[E_TAURI_HELPER_UNRESOLVED] {reason}. Tried: {}", trace.summary());
+                        return (Err(error), trace);
+                    }
+                    continue;
+                }
+            };
+            if !path.is_file() {
+                trace.record(candidate, Some(&path), CandidateStatus::Rejected, Some("file not found".to_string()));
+                continue;
+            }
+            let (command, args) = match candidate.runtime {
+                CandidateRuntime::Binary => (path.clone(), Vec::new()),
+                CandidateRuntime::Node if !cfg!(feature = "node-helper") => {
+                    let reason = "Node.js helpers need the `node-helper` cargo feature".to_string();
+                    trace.record(candidate, Some(&path), CandidateStatus::Rejected, Some(reason));
+                    continue;
+                }
+                CandidateRuntime::Node => match resolve_node_command() {
+                    Ok(node) => (node, vec![path.to_string_lossy().into_owned()]),
+                    Err(reason) => {
+                        trace.record(candidate, Some(&path), CandidateStatus::Rejected, Some(reason));
+                        continue;
+                    }
+                },
+            };
+            trace.record(candidate, Some(&path), CandidateStatus::Selected, None);
+            trace.mode = Some(candidate.mode().to_string());
+            trace.command = Some(command.to_string_lossy().into_owned());
+            trace.args = args.clone();
+            let resolved = ResolvedHelper {
+                command,
+                args,
+                mode: candidate.mode(),
+                runtime: candidate.runtime,
+            };
+            return (Ok(resolved), trace);
+        }
+        let error = format!(
+            "[E_TAURI_HELPER_UNRESOLVED] No helper runtime found. Build `dist-node/bridge-helper.js` or provide `CODEX_HELPER_BIN`. Tried: {}",
+            trace.summary()
+        );
+        (Err(error), trace)
+    }
+}
+
+pub fn resolve_helper(app: &AppHandle) -> Result<ResolvedHelper, String> {
+    match app.try_state::<HelperResolver>() {
+        Some(resolver) => resolver.resolve(app).0,
+        None => HelperResolver::default().resolve(app).0,
+    }
+}
+
+pub fn describe_resolution(app: &AppHandle) -> ResolutionTrace {
+    match app.try_state::<HelperResolver>() {
+        Some(resolver) => resolver.resolve(app).1,
+        None => HelperResolver::default().resolve(app).1,
+    }
+}
+
+fn locate(app: &AppHandle, candidate: &HelperCandidate) -> Result<Option<PathBuf>, String> {
+    match candidate.source {
+        CandidateSource::Env => match std::env::var(&candidate.value) {
+            Ok(value) => resolve_file_command(&value, &candidate.value).map(Some),
+            Err(_) => Ok(None),
+        },
+        CandidateSource::Resource => app
+            .path()
+            .resolve(&candidate.value, tauri::path::BaseDirectory::Resource)
+            .map(Some)
+            .map_err(|e| format!("resource path unavailable: {e}")),
+        CandidateSource::Path => {
+            let cwd = std::env::current_dir().map_err(|e| format!("current_dir failed: {e}"))?;
+            Ok(Some(cwd.join(&candidate.value)))
+        }
+    }
+}
+
+fn resolve_file_command(path: &str, env_name: &str) -> Result<PathBuf, String> {
+    let candidate = path.trim();
+    if candidate.is_empty() {
+        return Err(format!("{env_name} cannot be empty"));
+    }
+
+    let candidate_path = Path::new(candidate);
+    if is_explicit_path(candidate_path) {
+        if !candidate_path.exists() {
+            return Err(format!(
+                "{env_name} must reference an existing file. Missing path: {candidate}"
+            ));
+        }
+        if !candidate_path.is_file() {
+            return Err(format!("{env_name} must reference a file: {candidate}"));
+        }
+        Ok(candidate_path.to_path_buf())
+    } else {
+        find_binary_in_path(candidate).map_err(|e| format!("{env_name}: {e}"))
+    }
+}
+
+pub fn resolve_node_command() -> Result<PathBuf, String> {
+    let configured = std::env::var("CODEX_NODE_BIN").unwrap_or_else(|_| "node".to_string());
+    let command = configured.trim();
+    if command.is_empty() {
+        return Err("CODEX_NODE_BIN cannot be empty".to_string());
+    }
+
+    let command_path = Path::new(command);
+    if is_explicit_path(command_path) {
+        if !command_path.exists() {
+            return Err(format!(
+                "CODEX_NODE_BIN points to a missing file: {}",
+                command_path.to_string_lossy()
+            ));
+        }
+        if !command_path.is_file() {
+            return Err(format!("CODEX_NODE_BIN must point to a file: {command}"));
+        }
+        return Ok(command_path.to_path_buf());
+    }
+
+    if let Ok(canonical) = find_binary_in_path(command) {
+        return Ok(canonical);
+    }
+
+    Err(format!(
+        "Unable to find executable '{command}' in PATH. Set CODEX_NODE_BIN explicitly."
+    ))
+}
+
+fn is_explicit_path(path: &Path) -> bool {
+    if path.is_absolute() {
+        return true;
+    }
+    let raw = path.to_string_lossy();
+    raw.contains(std::path::MAIN_SEPARATOR)
+        || raw.contains('/')
+        || raw.contains('\\')
+}
+
+fn find_binary_in_path(command: &str) -> Result<PathBuf, String> {
+    let path = std::env::var_os("PATH").ok_or_else(|| "PATH environment variable is not set".to_string())?;
+
+    for path_dir in std::env::split_paths(&path) {
+        let candidate = path_dir.join(command);
+        if candidate.exists() {
+            return Ok(candidate);
+        }
+
+        let path_with_exts: &[&str] = if cfg!(windows) {
+            &[".exe", ".cmd", ".bat"]
+        } else {
+            &[""]
+        };
+
+        for suffix in path_with_exts {
+            let candidate = path_dir.join(format!("{command}{suffix}"));
+            if candidate.exists() {
+                return Ok(candidate);
+            }
+        }
+    }
+
+    Err(format!("command '{command}' not found in PATH"))
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use tokio::time::Duration;
+
+use crate::env_config;
+
+// Interrupting discards the in-flight turn, so it is opt-in per call or via
+// CODEX_HELPER_STOP_INTERRUPT_FIRST.
+const DEFAULT_INTERRUPT_FIRST: bool = false;
+const DEFAULT_INTERRUPT_TIMEOUT_MS: u64 = 3_000;
+const DEFAULT_ACK_TIMEOUT_MS: u64 = 5_000;
+const DEFAULT_EXIT_TIMEOUT_MS: u64 = 1_200;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS), ts(optional_fields = nullable))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct StopBridgeConfig {
+    pub interrupt_first: Option<bool>,
+    #[cfg_attr(test, ts(as = "Option<f64>"))]
+    pub interrupt_timeout_ms: Option<u64>,
+    #[cfg_attr(test, ts(as = "Option<f64>"))]
+    pub ack_timeout_ms: Option<u64>,
+    #[cfg_attr(test, ts(as = "Option<f64>"))]
+    pub exit_timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ShutdownConfig {
+    pub interrupt_first: bool,
+    pub interrupt_timeout: Duration,
+    pub ack_timeout: Duration,
+    pub exit_timeout: Duration,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            interrupt_first: DEFAULT_INTERRUPT_FIRST,
+            interrupt_timeout: Duration::from_millis(DEFAULT_INTERRUPT_TIMEOUT_MS),
+            ack_timeout: Duration::from_millis(DEFAULT_ACK_TIMEOUT_MS),
+            exit_timeout: Duration::from_millis(DEFAULT_EXIT_TIMEOUT_MS),
+        }
+    }
+}
+
+impl ShutdownConfig {
+    // Per-call values from `stop_bridge` win over `CODEX_HELPER_STOP_*`, which win over defaults.
+    pub fn resolve(overrides: Option<&StopBridgeConfig>) -> Result<Self, String> {
+        let overrides = overrides.cloned().unwrap_or_default();
+        let interrupt_first = match overrides.interrupt_first {
+            Some(value) => value,
+            None => env_config::flag("CODEX_HELPER_STOP_INTERRUPT_FIRST")?.unwrap_or(DEFAULT_INTERRUPT_FIRST),
+        };
+        Ok(Self {
+            interrupt_first,
+            interrupt_timeout: deadline(
+                overrides.interrupt_timeout_ms,
+                "CODEX_HELPER_STOP_INTERRUPT_TIMEOUT_MS",
+                DEFAULT_INTERRUPT_TIMEOUT_MS,
+            )?,
+            ack_timeout: deadline(overrides.ack_timeout_ms, "CODEX_HELPER_STOP_ACK_TIMEOUT_MS", DEFAULT_ACK_TIMEOUT_MS)?,
+            exit_timeout: deadline(
+                overrides.exit_timeout_ms,
+                "CODEX_HELPER_STOP_EXIT_TIMEOUT_MS",
+                DEFAULT_EXIT_TIMEOUT_MS,
+            )?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum StopPhase {
+    Interrupt,
+    Ack,
+    Exit,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct StopReport {
+    pub was_running: bool,
+    pub interrupted: bool,
+    pub acknowledged: bool,
+    pub exited: bool,
+    // First phase whose deadline expired; later phases still run.
+    pub timed_out_phase: Option<StopPhase>,
+    #[cfg_attr(test, ts(as = "f64"))]
+    pub elapsed_ms: u64,
+}
+
+impl StopReport {
+    pub fn record_timeout(&mut self, phase: StopPhase) {
+        if self.timed_out_phase.is_none() {
+            self.timed_out_phase = Some(phase);
+        }
+    }
+}
+
+fn deadline(override_ms: Option<u64>, env_name: &str, default_ms: u64) -> Result<Duration, String> {
+    let ms = match override_ms {
+        Some(ms) => ms,
+        None => env_config::number(env_name)?.unwrap_or(default_ms),
+    };
+    Ok(Duration::from_millis(ms))
+}
@@ -0,0 +1,12 @@
+// Host-only Tauri commands, registered after the generated bridge commands.
+// build.rs reads this list for the app manifest.
+pub const HOST_COMMANDS: &[&str] = &[
+    "verify_audit_log",
+    "list_sessions",
+    "stop_session",
+    "subscribe_events",
+    "unsubscribe_events",
+    "describe_helper_resolution",
+    "bridge_doctor",
+    "export_diagnostics",
+];
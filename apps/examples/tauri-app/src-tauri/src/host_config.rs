@@ -0,0 +1,28 @@
+use serde::Deserialize;
+use tauri::{AppHandle, Manager};
+
+const PLUGIN_CONFIG_KEY: &str = "codexHost";
+
+// Host settings from `plugins.codexHost` in tauri.conf.json. The file is embedded
+// at build time, so neither the webview nor the launch environment can change them.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HostConfig {
+    #[serde(default)]
+    pub keep_running_in_background: bool,
+    // Directories the helper may run in; see workspace_roots.
+    #[serde(default)]
+    pub workspace_roots: Vec<String>,
+    // Release builds spawn the helper without checking its pinned digest.
+    #[serde(default)]
+    pub allow_unverified_helper: bool,
+}
+
+impl HostConfig {
+    pub fn from_app_config(app: &AppHandle) -> Result<Self, String> {
+        let Some(value) = app.config().plugins.0.get(PLUGIN_CONFIG_KEY) else {
+            return Ok(Self::default());
+        };
+        serde_json::from_value(value.clone()).map_err(|e| format!("invalid plugins.{PLUGIN_CONFIG_KEY} config: {e}"))
+    }
+}
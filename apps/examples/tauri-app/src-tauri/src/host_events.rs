@@ -0,0 +1,19 @@
+// Events the host emits to the webview. Emit sites name events through these
+// constants; src/tauri_bindings.rs maps HOST_EVENTS to the payload types in the
+// generated TypeScript bindings.
+pub const BRIDGE_STATE: &str = "codex:bridge_state";
+pub const HELPER_EVENT: &str = "codex:event";
+pub const GLOBAL_MESSAGE: &str = "codex:global_message";
+pub const PROTOCOL_ERROR: &str = "codex:protocol_error";
+#[cfg_attr(not(feature = "instance-socket"), allow(dead_code))]
+pub const SECOND_INSTANCE: &str = "codex:second_instance";
+
+// Event name and the Rust type describing its payload.
+#[cfg(test)]
+pub const HOST_EVENTS: &[(&str, &str)] = &[
+    (BRIDGE_STATE, "BridgeStateEvent"),
+    (HELPER_EVENT, "HelperEvent"),
+    (GLOBAL_MESSAGE, "GlobalMessage"),
+    (PROTOCOL_ERROR, "ProtocolError"),
+    (SECOND_INSTANCE, "SecondInstance"),
+];
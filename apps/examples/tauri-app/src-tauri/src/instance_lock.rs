@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+#[cfg(feature = "instance-socket")]
+use crate::instance_socket;
+
+const INSTANCE_LOCK_FILE: &str = "instance.lock";
+const SESSION_LOCK_DIR: &str = "session-locks";
+
+// Payload of the `codex:second_instance` event; only sent with the instance socket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[cfg_attr(not(feature = "instance-socket"), allow(dead_code))]
+#[serde(rename_all = "camelCase")]
+pub struct SecondInstance {
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+}
+
+pub enum InstanceRole {
+    Primary(InstanceLock),
+    Secondary,
+}
+
+// Held for the lifetime of the primary instance; the OS releases the file lock
+// when the process dies, so a crash never leaves the app locked out.
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    pub fn acquire(app: &AppHandle) -> Result<InstanceRole, String> {
+        let data_dir = app_data_dir(app)?;
+        let Some(file) = try_lock_file(&data_dir.join(INSTANCE_LOCK_FILE))? else {
+            return Ok(InstanceRole::Secondary);
+        };
+        // Only forwarding degrades when the socket cannot be bound; the lock stays
+        // held so a second launch still cannot start its own instance.
+        #[cfg(feature = "instance-socket")]
+        if let Err(message) = instance_socket::listen(app.clone(), &data_dir) {
+            eprintln!("{message}; second launches will exit without reaching this instance");
+        }
+        Ok(InstanceRole::Primary(Self { _file: file }))
+    }
+
+    // Hands this launch's arguments (including deep links) to the primary instance.
+    #[cfg(feature = "instance-socket")]
+    pub fn forward_to_primary(app: &AppHandle) -> Result<(), String> {
+        instance_socket::forward(&app_data_dir(app)?)
+    }
+
+    #[cfg(not(feature = "instance-socket"))]
+    pub fn forward_to_primary(_app: &AppHandle) -> Result<(), String> {
+        Err(crate::feature_disabled(
+            "instance-socket",
+            "forwarding launch arguments to the running instance",
+        ))
+    }
+}
+
+pub struct SessionLock {
+    _file: File,
+}
+
+impl SessionLock {
+    pub fn acquire(app: &AppHandle, session_id: &str) -> Result<Self, String> {
+        let dir = app_data_dir(app)?.join(SESSION_LOCK_DIR);
+        fs::create_dir_all(&dir).map_err(|e| format!("failed to create session lock dir {}: {e}", dir.display()))?;
+        let digest = hex::encode(Sha256::digest(session_id.as_bytes()));
+        let path = dir.join(format!("{}.lock", &digest[..32]));
+        match try_lock_file(&path)? {
+            Some(file) => Ok(Self { _file: file }),
+            None => Err(format!(
+                "[E_TAURI_SESSION_LOCKED] session \"{session_id}\" is already running in another process"
+            )),
+        }
+    }
+}
+
+fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("failed to resolve app data dir for instance lock: {e}"))
+}
+
+fn try_lock_file(path: &Path) -> Result<Option<File>, String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("failed to create lock dir {}: {e}", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| format!("failed to open lock file {}: {e}", path.display()))?;
+    match file.try_lock() {
+        Ok(()) => {
+            // Owner pid, for diagnostics only.
+            let _ = file.set_len(0).and_then(|()| write!(file, "{}", std::process::id()));
+            Ok(Some(file))
+        }
+        Err(TryLockError::WouldBlock) => Ok(None),
+        Err(TryLockError::Error(error)) => Err(format!("failed to lock {}: {error}", path.display())),
+    }
+}
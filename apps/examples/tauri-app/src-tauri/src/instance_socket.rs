@@ -0,0 +1,109 @@
+use std::io::Write;
+use std::path::Path;
+#[cfg(unix)]
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter};
+use tokio::io::AsyncReadExt;
+
+use crate::host_events;
+use crate::instance_lock::SecondInstance;
+use crate::main_window;
+
+const MAX_FORWARD_BYTES: u64 = 64 * 1024;
+
+pub fn forward(data_dir: &Path) -> Result<(), String> {
+    let message = SecondInstance {
+        args: std::env::args().skip(1).collect(),
+        cwd: std::env::current_dir().ok().map(|cwd| cwd.to_string_lossy().into_owned()),
+    };
+    let encoded = serde_json::to_vec(&message).map_err(|e| format!("failed to encode launch arguments: {e}"))?;
+    let mut stream = connect(data_dir)?;
+    stream
+        .write_all(&encoded)
+        .map_err(|e| format!("failed to forward launch arguments to running instance: {e}"))
+}
+
+async fn handle_forwarded<S: tokio::io::AsyncRead + Unpin>(app: &AppHandle, stream: S) {
+    let mut buffer = Vec::new();
+    if stream.take(MAX_FORWARD_BYTES).read_to_end(&mut buffer).await.is_err() {
+        return;
+    }
+    if let Ok(message) = serde_json::from_slice::<SecondInstance>(&buffer) {
+        let _ = app.emit(host_events::SECOND_INSTANCE, message);
+    }
+    let _ = main_window::show(app);
+}
+
+#[cfg(unix)]
+fn socket_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("instance.sock")
+}
+
+#[cfg(unix)]
+pub fn listen(app: AppHandle, data_dir: &Path) -> Result<(), String> {
+    let path = socket_path(data_dir);
+    // Holding the instance lock means any socket file left here is stale.
+    let _ = std::fs::remove_file(&path);
+    let std_listener = std::os::unix::net::UnixListener::bind(&path)
+        .map_err(|e| format!("failed to bind instance socket {}: {e}", path.display()))?;
+    std_listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("failed to configure instance socket: {e}"))?;
+    tauri::async_runtime::spawn(async move {
+        let Ok(listener) = tokio::net::UnixListener::from_std(std_listener) else {
+            return;
+        };
+        while let Ok((stream, _)) = listener.accept().await {
+            handle_forwarded(&app, stream).await;
+        }
+    });
+    Ok(())
+}
+
+#[cfg(unix)]
+fn connect(data_dir: &Path) -> Result<std::os::unix::net::UnixStream, String> {
+    let path = socket_path(data_dir);
+    std::os::unix::net::UnixStream::connect(&path)
+        .map_err(|e| format!("failed to reach running instance at {}: {e}", path.display()))
+}
+
+#[cfg(windows)]
+fn pipe_name(data_dir: &Path) -> String {
+    use sha2::{Digest, Sha256};
+
+    let digest = hex::encode(Sha256::digest(data_dir.to_string_lossy().as_bytes()));
+    format!(r"\\.\pipe\codex-local-{}", &digest[..32])
+}
+
+#[cfg(windows)]
+pub fn listen(app: AppHandle, data_dir: &Path) -> Result<(), String> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let name = pipe_name(data_dir);
+    tauri::async_runtime::spawn(async move {
+        // Pipes need the tokio reactor, so they are created inside the task.
+        let Ok(mut server) = ServerOptions::new().first_pipe_instance(true).create(&name) else {
+            return;
+        };
+        loop {
+            if server.connect().await.is_err() {
+                return;
+            }
+            let Ok(next) = ServerOptions::new().create(&name) else {
+                return;
+            };
+            let connected = std::mem::replace(&mut server, next);
+            handle_forwarded(&app, connected).await;
+        }
+    });
+    Ok(())
+}
+
+#[cfg(windows)]
+fn connect(data_dir: &Path) -> Result<std::fs::File, String> {
+    let name = pipe_name(data_dir);
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(&name)
+        .map_err(|e| format!("failed to reach running instance at {name}: {e}"))
+}
@@ -1,19 +1,77 @@
+#[cfg(feature = "approval-policy")]
+mod approval_policy;
+mod approval_request;
+#[cfg(feature = "audit-log")]
+mod audit_log;
+mod audit_verification;
+mod bridge_doctor;
 mod bridge_process;
 mod bridge_contract_generated;
 mod bridge_dispatch_generated;
+#[cfg(test)]
+mod contract_consistency;
+mod diagnostics_bundle;
+mod env_config;
+mod event_routing;
+mod exit_coordinator;
+mod generation_waiter;
+mod helper_heartbeat;
+mod helper_hot_reload;
+mod helper_integrity;
+mod helper_process_policy;
+mod helper_readiness;
+mod helper_resolution;
+mod helper_shutdown;
+#[cfg(test)]
+mod host_commands;
+mod host_config;
+mod host_events;
+mod instance_lock;
+#[cfg(feature = "instance-socket")]
+mod instance_socket;
+// Only the tray, the instance socket and the macOS dock reopen windows.
+#[cfg(any(feature = "instance-socket", feature = "tray", target_os = "macos"))]
+mod main_window;
+#[cfg(feature = "node-helper")]
+mod node_runtime;
+mod payload_schema;
+mod process_tree;
+mod protocol_types;
+mod redaction;
+mod session_registry;
+#[cfg(test)]
+mod tauri_bindings;
+#[cfg(feature = "tray")]
+mod tray;
+mod workspace_roots;
 include!("bridge_invoke_handlers_generated.rs");
 
-use bridge_process::{AppBridgeState, BridgeRuntime};
 use bridge_contract_generated::{BRIDGE_COMMANDS, HELPER_COMMANDS};
 use bridge_dispatch_generated::HELPER_FORWARD_TAURI_COMMANDS;
 use serde::Deserialize;
 use serde_json::json;
+use session_registry::AppBridgeState;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::{Emitter, Manager, RunEvent, State, WindowEvent};
+use tauri::{Manager, RunEvent, State, WindowEvent};
 
 static START_TRACE_SEQ: AtomicU64 = AtomicU64::new(1);
 
+// Host state the generated forwarding commands dispatch through, and the typed
+// payloads they take, schema-checked before they deserialize.
+type BridgeHostState = AppBridgeState;
+use payload_schema::Validated;
+use protocol_types::{
+    CancelLoginAccountParams, GetAccountParams, LoginAccountConfig, RespondChatgptAuthTokensRefreshConfig,
+    RespondCommandApprovalConfig, RespondFileChangeApprovalConfig, RespondToolUserInputConfig, SetDisabledToolsConfig,
+};
+include!("bridge_commands_generated.rs");
+
+// Error for host commands whose subsystem was left out of this build.
+fn feature_disabled(feature: &str, subsystem: &str) -> String {
+    format!("[E_TAURI_FEATURE_DISABLED] {subsystem} is not compiled into this host; rebuild with the `{feature}` cargo feature.")
+}
+
 fn now_unix_ms() -> u128 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -22,6 +80,7 @@ fn now_unix_ms() -> u128 {
 }
 
 #[derive(Debug, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS), ts(optional_fields = nullable))]
 #[serde(rename_all = "camelCase")]
 struct StartBridgeConfig {
     convex_url: String,
@@ -31,35 +90,49 @@ struct StartBridgeConfig {
     model: Option<String>,
     cwd: Option<String>,
     disabled_tools: Option<Vec<String>>,
+    #[cfg_attr(test, ts(as = "Option<f64>"))]
     delta_throttle_ms: Option<u64>,
     save_stream_deltas: Option<bool>,
+    helper_process: Option<helper_process_policy::HelperProcessConfig>,
+    #[cfg_attr(test, ts(as = "Option<f64>"))]
+    ready_timeout_ms: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS), ts(optional_fields = nullable))]
 #[serde(rename_all = "camelCase")]
 struct OpenThreadConfig {
     strategy: String,
     thread_id: Option<String>,
     model: Option<String>,
     cwd: Option<String>,
+    #[cfg_attr(test, ts(type = "unknown", optional))]
     dynamic_tools: Option<serde_json::Value>,
 }
 
 #[tauri::command]
 async fn start_bridge(
     app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
     state: State<'_, AppBridgeState>,
+    session_id: Option<String>,
     config: StartBridgeConfig,
 ) -> Result<(), String> {
+    let session_id = session_registry::start_session_id(session_id.as_deref(), &config.session_id)?;
     let trace_id = START_TRACE_SEQ.fetch_add(1, Ordering::Relaxed);
+    let runtime = state.runtime_for_start(session_id).await;
+    // The starting window receives the session's events without subscribing.
+    app.state::<event_routing::EventRouter>()
+        .record_start(window.label(), runtime.session_id());
     let source = config
         .start_source
         .clone()
         .unwrap_or_else(|| "unspecified".to_string());
-    let snapshot_before = state.runtime.snapshot().await;
+    let snapshot_before = runtime.snapshot().await;
 
-    let _ = app.emit(
-        "codex:global_message",
+    emit_start_trace(
+        &app,
+        runtime.session_id(),
         serde_json::json!({
             "kind": "bridge/start_trace",
             "phase": "received",
@@ -72,29 +145,41 @@ async fn start_bridge(
         }),
     );
 
-    let start_result = state
-        .runtime
-        .start(
-            app.clone(),
-            bridge_process::HelperStartPayload {
-                convex_url: config.convex_url,
-                actor: config.actor,
-                session_id: config.session_id,
-                disabled_tools: config.disabled_tools,
-                model: config.model,
-                cwd: config.cwd,
-                delta_throttle_ms: config.delta_throttle_ms,
-                save_stream_deltas: config.save_stream_deltas,
-                thread_strategy: None,
-                thread_id: None,
-            },
-        )
-        .await;
+    let start_result = match workspace_roots::resolve_helper_cwd(&app, config.cwd.as_deref()) {
+        Ok(cwd) => {
+            runtime
+                .start(
+                    app.clone(),
+                    bridge_process::HelperStartPayload {
+                        convex_url: config.convex_url,
+                        actor: config.actor,
+                        session_id: config.session_id,
+                        disabled_tools: config.disabled_tools,
+                        model: config.model,
+                        cwd: Some(cwd),
+                        delta_throttle_ms: config.delta_throttle_ms,
+                        save_stream_deltas: config.save_stream_deltas,
+                        thread_strategy: None,
+                        thread_id: None,
+                    },
+                    bridge_process::HelperLaunchOptions {
+                        process: config.helper_process,
+                        ready_timeout_ms: config.ready_timeout_ms,
+                    },
+                )
+                .await
+        }
+        Err(error) => Err(error.into()),
+    };
 
+    if start_result.is_err() {
+        state.discard_if_idle(runtime.session_id()).await;
+    }
     match &start_result {
         Ok(()) => {
-            let _ = app.emit(
-                "codex:global_message",
+            emit_start_trace(
+                &app,
+                runtime.session_id(),
                 serde_json::json!({
                     "kind": "bridge/start_trace",
                     "phase": "result",
@@ -106,8 +191,9 @@ async fn start_bridge(
             );
         }
         Err(message) => {
-            let _ = app.emit(
-                "codex:global_message",
+            emit_start_trace(
+                &app,
+                runtime.session_id(),
                 serde_json::json!({
                     "kind": "bridge/start_trace",
                     "phase": "result",
@@ -115,7 +201,7 @@ async fn start_bridge(
                     "tsMs": now_unix_ms(),
                     "source": source,
                     "status": "error",
-                    "message": message,
+                    "message": redaction::redact_text(message),
                 }),
             );
         }
@@ -124,14 +210,31 @@ async fn start_bridge(
     start_result
 }
 
+// Start traces describe one session, so they follow that session's routing.
+#[cfg(feature = "start-trace")]
+fn emit_start_trace(app: &tauri::AppHandle, session_id: &str, mut trace: serde_json::Value) {
+    if let Some(object) = trace.as_object_mut() {
+        object.insert("sessionId".to_string(), json!(session_id));
+    }
+    let _ = app
+        .state::<event_routing::EventRouter>()
+        .emit(app, host_events::GLOBAL_MESSAGE, session_id, trace);
+}
+
+#[cfg(not(feature = "start-trace"))]
+fn emit_start_trace(_app: &tauri::AppHandle, _session_id: &str, _trace: serde_json::Value) {}
+
 #[tauri::command]
 async fn open_thread(
     app: tauri::AppHandle,
     state: State<'_, AppBridgeState>,
+    session_id: Option<String>,
     config: OpenThreadConfig,
 ) -> Result<(), String> {
+    let cwd = workspace_roots::resolve_helper_cwd(&app, config.cwd.as_deref())?;
     state
-        .runtime
+        .runtime(session_id.as_deref())
+        .await?
         .forward_tauri_json_command(
             app,
             "open_thread",
@@ -139,7 +242,7 @@ async fn open_thread(
                 "strategy": config.strategy,
                 "threadHandle": config.thread_id,
                 "model": config.model,
-                "cwd": config.cwd,
+                "cwd": cwd,
                 "dynamicTools": config.dynamic_tools,
             }),
         )
@@ -147,166 +250,179 @@ async fn open_thread(
 }
 
 #[tauri::command]
-async fn refresh_local_threads(
+async fn stop_bridge(
     app: tauri::AppHandle,
     state: State<'_, AppBridgeState>,
-) -> Result<(), String> {
-    state
-        .runtime
-        .forward_tauri_json_command(app, "refresh_local_threads", json!({}))
-        .await
+    session_id: Option<String>,
+    config: Option<helper_shutdown::StopBridgeConfig>,
+) -> Result<helper_shutdown::StopReport, String> {
+    let shutdown = helper_shutdown::ShutdownConfig::resolve(config.as_ref())?;
+    state.stop_session(app, session_id.as_deref(), shutdown).await
 }
 
 #[tauri::command]
-async fn send_user_turn(
-    app: tauri::AppHandle,
+async fn get_bridge_state(
     state: State<'_, AppBridgeState>,
-    text: String,
-) -> Result<(), String> {
-    state
-        .runtime
-        .forward_tauri_json_command(app, "send_user_turn", json!({ "text": text }))
-        .await
+    session_id: Option<String>,
+) -> Result<bridge_process::BridgeStateSnapshot, String> {
+    state.snapshot(session_id.as_deref()).await
 }
 
 #[tauri::command]
-async fn interrupt_turn(app: tauri::AppHandle, state: State<'_, AppBridgeState>) -> Result<(), String> {
-    state
-        .runtime
-        .forward_tauri_json_command(app, "interrupt_turn", json!({}))
-        .await
-}
-
-#[tauri::command]
-async fn respond_command_approval(
-    app: tauri::AppHandle,
-    state: State<'_, AppBridgeState>,
-    config: serde_json::Value,
-) -> Result<(), String> {
-    state.runtime.forward_tauri_json_command(app, "respond_command_approval", config).await
+async fn verify_audit_log(app: tauri::AppHandle) -> Result<audit_verification::AuditVerification, String> {
+    audit_verification::verify(&app).await
 }
 
 #[tauri::command]
-async fn respond_file_change_approval(
-    app: tauri::AppHandle,
-    state: State<'_, AppBridgeState>,
-    config: serde_json::Value,
-) -> Result<(), String> {
-    state.runtime.forward_tauri_json_command(app, "respond_file_change_approval", config).await
+async fn list_sessions(state: State<'_, AppBridgeState>) -> Result<Vec<session_registry::SessionSummary>, String> {
+    Ok(state.list().await)
 }
 
 #[tauri::command]
-async fn respond_tool_user_input(
+async fn stop_session(
     app: tauri::AppHandle,
     state: State<'_, AppBridgeState>,
-    config: serde_json::Value,
-) -> Result<(), String> {
-    state.runtime.forward_tauri_json_command(app, "respond_tool_user_input", config).await
-}
-
-#[tauri::command]
-async fn read_account(
-    app: tauri::AppHandle,
-    state: State<'_, AppBridgeState>,
-    config: serde_json::Value,
-) -> Result<(), String> {
-    state.runtime.forward_tauri_json_command(app, "read_account", config).await
-}
-
-#[tauri::command]
-async fn login_account(
-    app: tauri::AppHandle,
-    state: State<'_, AppBridgeState>,
-    config: serde_json::Value,
-) -> Result<(), String> {
-    state.runtime.forward_tauri_json_command(app, "login_account", config).await
+    session_id: String,
+    config: Option<helper_shutdown::StopBridgeConfig>,
+) -> Result<helper_shutdown::StopReport, String> {
+    let shutdown = helper_shutdown::ShutdownConfig::resolve(config.as_ref())?;
+    state.stop_session(app, Some(&session_id), shutdown).await
 }
 
 #[tauri::command]
-async fn cancel_account_login(
-    app: tauri::AppHandle,
-    state: State<'_, AppBridgeState>,
-    config: serde_json::Value,
-) -> Result<(), String> {
-    state.runtime.forward_tauri_json_command(app, "cancel_account_login", config).await
+fn subscribe_events(
+    window: tauri::WebviewWindow,
+    router: State<'_, event_routing::EventRouter>,
+    config: Option<event_routing::EventSubscriptionConfig>,
+) {
+    router.subscribe(window.label(), config.unwrap_or_default());
 }
 
 #[tauri::command]
-async fn logout_account(
-    app: tauri::AppHandle,
-    state: State<'_, AppBridgeState>,
-) -> Result<(), String> {
-    state
-        .runtime
-        .forward_tauri_json_command(app, "logout_account", json!({}))
-        .await
+fn unsubscribe_events(window: tauri::WebviewWindow, router: State<'_, event_routing::EventRouter>) {
+    router.unsubscribe(window.label());
 }
 
 #[tauri::command]
-async fn read_account_rate_limits(
-    app: tauri::AppHandle,
-    state: State<'_, AppBridgeState>,
-) -> Result<(), String> {
-    state
-        .runtime
-        .forward_tauri_json_command(app, "read_account_rate_limits", json!({}))
-        .await
+fn describe_helper_resolution(app: tauri::AppHandle) -> helper_resolution::ResolutionTrace {
+    helper_resolution::describe_resolution(&app)
 }
 
 #[tauri::command]
-async fn respond_chatgpt_auth_tokens_refresh(
+async fn export_diagnostics(
     app: tauri::AppHandle,
     state: State<'_, AppBridgeState>,
-    config: serde_json::Value,
-) -> Result<(), String> {
-    state.runtime.forward_tauri_json_command(app, "respond_chatgpt_auth_tokens_refresh", config).await
-}
-
-#[tauri::command]
-async fn stop_bridge(app: tauri::AppHandle, state: State<'_, AppBridgeState>) -> Result<(), String> {
-    state.runtime.stop(app).await
-}
-
-#[tauri::command]
-async fn get_bridge_state(state: State<'_, AppBridgeState>) -> Result<bridge_process::BridgeStateSnapshot, String> {
-    Ok(state.runtime.snapshot().await)
+) -> Result<diagnostics_bundle::DiagnosticsExport, String> {
+    diagnostics_bundle::export(&app, &state).await
 }
 
 #[tauri::command]
-async fn set_disabled_tools(
+async fn bridge_doctor(
     app: tauri::AppHandle,
-    state: State<'_, AppBridgeState>,
-    config: serde_json::Value,
-) -> Result<(), String> {
-    state.runtime.forward_tauri_json_command(app, "set_disabled_tools", config).await
+    config: Option<bridge_doctor::DoctorConfig>,
+) -> Result<bridge_doctor::DoctorReport, String> {
+    Ok(bridge_doctor::run(&app, config.unwrap_or_default()).await)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    debug_assert_eq!(BRIDGE_COMMANDS.len(), 17);
+    // Full cross-checks against build.rs and the capability file live in contract_consistency.
+    debug_assert!(!BRIDGE_COMMANDS.is_empty());
     debug_assert!(!HELPER_COMMANDS.is_empty());
     debug_assert!(!HELPER_FORWARD_TAURI_COMMANDS.is_empty());
     let app = tauri::Builder::default()
-        .manage(AppBridgeState {
-            runtime: BridgeRuntime::default(),
+        .manage(AppBridgeState::default())
+        .manage(diagnostics_bundle::DiagnosticsRecorder::default())
+        .manage(event_routing::EventRouter::default())
+        .invoke_handler(bridge_generate_handler!(
+            verify_audit_log,
+            list_sessions,
+            stop_session,
+            subscribe_events,
+            unsubscribe_events,
+            describe_helper_resolution,
+            bridge_doctor,
+            export_diagnostics
+        ))
+        .setup(|app| {
+            let host_config = host_config::HostConfig::from_app_config(app.handle())?;
+            let exit_coordinator = exit_coordinator::ExitCoordinator::new(&host_config)?;
+            app.manage(exit_coordinator);
+            app.manage(host_config);
+            #[cfg(feature = "audit-log")]
+            app.manage(audit_log::SharedAuditLog::default());
+            // A resolver managed on the builder takes precedence over tauri.conf.json.
+            if app.try_state::<helper_resolution::HelperResolver>().is_none() {
+                let resolver = helper_resolution::HelperResolver::from_app_config(app.handle())?;
+                app.manage(resolver);
+            }
+            // `--doctor` writes the diagnostics report as JSON to the app log dir, echoes it
+            // to stdout and exits; it runs before the instance lock so it also works while
+            // another instance is open.
+            if std::env::args().skip(1).any(|arg| arg == "--doctor") {
+                let report = tauri::async_runtime::block_on(bridge_doctor::run(app.handle(), Default::default()));
+                let written = serde_json::to_string_pretty(&report)
+                    .map_err(|e| format!("failed to encode doctor report: {e}"))
+                    .and_then(|encoded| {
+                        println!("{encoded}");
+                        bridge_doctor::write_report(app.handle(), &encoded)
+                    });
+                match written {
+                    Ok(path) => eprintln!("bridge doctor report written to {}", path.display()),
+                    Err(message) => eprintln!("{message}"),
+                }
+                std::process::exit(if report.status == bridge_doctor::CheckStatus::Fail { 1 } else { 0 });
+            }
+            match instance_lock::InstanceLock::acquire(app.handle()) {
+                Ok(instance_lock::InstanceRole::Primary(lock)) => {
+                    app.manage(lock);
+                    // Helpers orphaned by a host that was killed outright are reaped before any
+                    // new start. Only the lock holder sweeps: without it, the pids on record may
+                    // belong to a live instance. A failed sweep must not block startup.
+                    let _ = process_tree::HelperPidFile::for_app(app.handle()).and_then(|pid_file| pid_file.sweep_stale());
+                }
+                Ok(instance_lock::InstanceRole::Secondary) => {
+                    let code = match instance_lock::InstanceLock::forward_to_primary(app.handle()) {
+                        Ok(()) => 0,
+                        Err(message) => {
+                            eprintln!("{message}");
+                            1
+                        }
+                    };
+                    std::process::exit(code);
+                }
+                // Without the lock the app still runs; only the single-instance guarantee is lost.
+                Err(message) => eprintln!("{message}"),
+            }
+            #[cfg(feature = "tray")]
+            if app.state::<host_config::HostConfig>().keep_running_in_background {
+                tray::install(app.handle())?;
+            }
+            Ok(())
         })
-        .invoke_handler(bridge_generate_handler!())
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
 
     app.run(|app, event| match event {
         RunEvent::WindowEvent {
-            event: WindowEvent::CloseRequested { .. },
+            label,
+            event: WindowEvent::Destroyed,
             ..
+        } => app.state::<event_routing::EventRouter>().forget_window(&label),
+        RunEvent::ExitRequested { code, api, .. } => {
+            app.state::<exit_coordinator::ExitCoordinator>()
+                .on_exit_requested(app, &api, code);
         }
-        | RunEvent::ExitRequested { .. }
-        | RunEvent::Exit => {
-            tauri::async_runtime::block_on(async {
-                let app_handle = app.clone();
-                let state_handle = app_handle.clone();
-                let state = state_handle.state::<AppBridgeState>();
-                let _ = state.runtime.stop(app_handle).await;
-            });
+        RunEvent::Exit => app.state::<exit_coordinator::ExitCoordinator>().on_exit(app),
+        // Clicking the dock icon after the last window closed in background mode.
+        #[cfg(target_os = "macos")]
+        RunEvent::Reopen {
+            has_visible_windows: false,
+            ..
+        } => {
+            if let Err(message) = main_window::show(app) {
+                eprintln!("{message}");
+            }
         }
         _ => {}
     });
@@ -0,0 +1,25 @@
+use tauri::{AppHandle, Manager};
+
+// Brings the main window to the front, recreating it from tauri.conf.json when
+// it was closed while the app kept running in the background.
+pub fn show(app: &AppHandle) -> Result<(), String> {
+    let window = app
+        .get_webview_window("main")
+        .or_else(|| app.webview_windows().into_values().next());
+    if let Some(window) = window {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(());
+    }
+    let config = app
+        .config()
+        .app
+        .windows
+        .first()
+        .ok_or_else(|| "tauri.conf.json declares no window to reopen".to_string())?;
+    tauri::WebviewWindowBuilder::from_config(app, config)
+        .and_then(|builder| builder.build())
+        .map(|_| ())
+        .map_err(|e| format!("failed to reopen main window: {e}"))
+}
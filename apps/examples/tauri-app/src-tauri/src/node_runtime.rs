@@ -0,0 +1,225 @@
+use semver::{Version, VersionReq};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::helper_hot_reload::{self, Fingerprint};
+
+// `engines.node` from the helper's package.json, recorded by build.rs.
+const VERSION_REQ: Option<&str> = option_env!("CODEX_NODE_VERSION_REQ");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeRuntimeErrorCode {
+    NodeTooOld,
+    NodeNotExecutable,
+    InvalidRange,
+}
+
+impl NodeRuntimeErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NodeRuntimeErrorCode::NodeTooOld => "node_too_old",
+            NodeRuntimeErrorCode::NodeNotExecutable => "node_not_executable",
+            NodeRuntimeErrorCode::InvalidRange => "node_range_invalid",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NodeRuntimeError {
+    pub code: NodeRuntimeErrorCode,
+    pub message: String,
+}
+
+impl NodeRuntimeError {
+    fn not_executable(message: String) -> Self {
+        Self {
+            code: NodeRuntimeErrorCode::NodeNotExecutable,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for NodeRuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code.as_str(), self.message)
+    }
+}
+
+type ProbeCache = HashMap<PathBuf, (Option<Fingerprint>, Result<Version, NodeRuntimeError>)>;
+
+// Probes are cached per binary and redone when the file changes, e.g. after a
+// Node upgrade in place.
+fn probe_cache() -> &'static Mutex<ProbeCache> {
+    static CACHE: OnceLock<Mutex<ProbeCache>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+// Checks that `node` can be executed and satisfies the helper's declared range.
+pub fn check_node(node: &Path) -> Result<Version, NodeRuntimeError> {
+    let version = probe_version(node)?;
+    let Some(range) = VERSION_REQ.map(str::trim).filter(|range| !range.is_empty()) else {
+        return Ok(version);
+    };
+    let alternatives = parse_range(range).map_err(|message| NodeRuntimeError {
+        code: NodeRuntimeErrorCode::InvalidRange,
+        message,
+    })?;
+    if alternatives.iter().any(|req| req.matches(&version)) {
+        return Ok(version);
+    }
+    Err(NodeRuntimeError {
+        code: NodeRuntimeErrorCode::NodeTooOld,
+        message: format!(
+            "Node.js v{version} at {} does not satisfy the helper's requirement \"{range}\". Install a newer Node.js or point CODEX_NODE_BIN at one.",
+            node.display()
+        ),
+    })
+}
+
+// Translates an npm range into semver requirements, one per `||` alternative.
+// npm separates comparators with spaces and writes `A - B` for inclusive
+// ranges; semver wants commas and explicit bounds. Anything left that semver
+// cannot parse is an error, so a typo never accepts every runtime.
+fn parse_range(range: &str) -> Result<Vec<VersionReq>, String> {
+    range
+        .split("||")
+        .map(|alternative| {
+            let translated = translate_alternative(alternative.trim());
+            VersionReq::parse(&translated)
+                .map_err(|e| format!("the helper's engines.node range \"{range}\" is not a supported npm range: {e}"))
+        })
+        .collect()
+}
+
+fn translate_alternative(alternative: &str) -> String {
+    if alternative.is_empty() || alternative == "x" || alternative == "X" {
+        return "*".to_string();
+    }
+    if let Some((low, high)) = alternative.split_once(" - ") {
+        return format!(">={}, <={}", hyphen_bound(low), hyphen_bound(high));
+    }
+    let mut comparators: Vec<String> = Vec::new();
+    let mut pending_operator = String::new();
+    for token in alternative.split_whitespace() {
+        // `>= 18` is valid npm; keep the operator with its version.
+        if token.chars().all(|c| matches!(c, '<' | '>' | '=' | '~' | '^')) {
+            pending_operator.push_str(token);
+            continue;
+        }
+        comparators.push(format!("{}{token}", std::mem::take(&mut pending_operator)));
+    }
+    if !pending_operator.is_empty() {
+        comparators.push(pending_operator);
+    }
+    comparators.join(", ")
+}
+
+// `18.x - 20.x` means `>=18 <21`; semver reads partial bounds the same way.
+fn hyphen_bound(bound: &str) -> &str {
+    let mut bound = bound.trim().trim_start_matches(['v', '=']);
+    while let Some(rest) = [".x", ".X", ".*"].iter().find_map(|wildcard| bound.strip_suffix(wildcard)) {
+        bound = rest;
+    }
+    bound
+}
+
+fn probe_version(node: &Path) -> Result<Version, NodeRuntimeError> {
+    let fingerprint = helper_hot_reload::fingerprint(node);
+    let mut cache = probe_cache().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((cached_fingerprint, result)) = cache.get(node) {
+        if *cached_fingerprint == fingerprint {
+            return result.clone();
+        }
+    }
+    let result = run_version_probe(node);
+    cache.insert(node.to_path_buf(), (fingerprint, result.clone()));
+    result
+}
+
+fn run_version_probe(node: &Path) -> Result<Version, NodeRuntimeError> {
+    ensure_executable(node)?;
+    let output = std::process::Command::new(node)
+        .arg("--version")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| NodeRuntimeError::not_executable(format!("failed to run {} --version: {e}", node.display())))?;
+    if !output.status.success() {
+        return Err(NodeRuntimeError::not_executable(format!(
+            "{} --version exited with {}",
+            node.display(),
+            output.status
+        )));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let reported = stdout.lines().next().unwrap_or_default().trim();
+    Version::parse(reported.trim_start_matches('v')).map_err(|e| {
+        NodeRuntimeError::not_executable(format!(
+            "{} --version reported \"{reported}\", which is not a Node.js version: {e}",
+            node.display()
+        ))
+    })
+}
+
+#[cfg(unix)]
+fn ensure_executable(node: &Path) -> Result<(), NodeRuntimeError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = std::fs::metadata(node)
+        .map_err(|e| NodeRuntimeError::not_executable(format!("failed to stat {}: {e}", node.display())))?;
+    if metadata.permissions().mode() & 0o111 == 0 {
+        return Err(NodeRuntimeError::not_executable(format!(
+            "{} is not executable (check its file permissions)",
+            node.display()
+        )));
+    }
+    Ok(())
+}
+
+// Windows has no execute bit; running `--version` is the check.
+#[cfg(not(unix))]
+fn ensure_executable(_node: &Path) -> Result<(), NodeRuntimeError> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satisfies(range: &str, version: &str) -> bool {
+        let version = Version::parse(version).unwrap();
+        parse_range(range).unwrap().iter().any(|req| req.matches(&version))
+    }
+
+    #[test]
+    fn space_separated_comparators_must_all_match() {
+        assert!(satisfies(">=18 <21", "20.11.0"));
+        assert!(!satisfies(">=18 <21", "21.0.0"));
+        assert!(!satisfies(">=18 <21", "16.20.0"));
+        assert!(satisfies(">= 18.17.0", "18.17.0"));
+    }
+
+    #[test]
+    fn hyphen_ranges_include_both_partial_bounds() {
+        assert!(satisfies("18.x - 20.x", "18.0.0"));
+        assert!(satisfies("18.x - 20.x", "20.99.0"));
+        assert!(!satisfies("18.x - 20.x", "21.0.0"));
+        assert!(!satisfies("18.17 - 20", "18.16.0"));
+    }
+
+    #[test]
+    fn unions_pass_when_any_alternative_matches() {
+        assert!(satisfies("^18.17 || >=20", "18.19.0"));
+        assert!(satisfies("^18.17 || >=20", "22.1.0"));
+        assert!(!satisfies("^18.17 || >=20", "19.0.0"));
+    }
+
+    #[test]
+    fn unparseable_ranges_are_rejected() {
+        for range in ["node 18", ">=eighteen", "^18 || lts"] {
+            let error = parse_range(range).unwrap_err();
+            assert!(error.contains(&format!("\"{range}\"")), "{error}");
+        }
+    }
+}
@@ -0,0 +1,549 @@
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+// Schemas are read straight from the component package so host validation can
+// never drift from the protocol snapshot the helper was generated against.
+macro_rules! protocol_schema {
+    ($file:literal) => {
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../../../packages/codex-local-component/src/protocol/schemas/",
+            $file
+        ))
+    };
+}
+
+// Host-only payload schemas, shared with build.rs's type generation.
+macro_rules! host_schema {
+    ($file:literal) => {
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/schemas/", $file))
+    };
+}
+
+struct CommandSchema {
+    schema: &'static str,
+    // Validate `config[field]` instead of the whole payload.
+    field: Option<&'static str>,
+    // Server-request responses carry the JSON-RPC id alongside the response body.
+    request_id: bool,
+}
+
+const COMMAND_SCHEMAS: &[(&str, CommandSchema)] = &[
+    (
+        "respond_command_approval",
+        CommandSchema {
+            schema: "CommandExecutionRequestApprovalResponse",
+            field: None,
+            request_id: true,
+        },
+    ),
+    (
+        "respond_file_change_approval",
+        CommandSchema {
+            schema: "FileChangeRequestApprovalResponse",
+            field: None,
+            request_id: true,
+        },
+    ),
+    (
+        "respond_tool_user_input",
+        CommandSchema {
+            schema: "ToolRequestUserInputResponse",
+            field: None,
+            request_id: true,
+        },
+    ),
+    (
+        "respond_chatgpt_auth_tokens_refresh",
+        CommandSchema {
+            schema: "ChatgptAuthTokensRefreshResponse",
+            field: None,
+            request_id: true,
+        },
+    ),
+    (
+        "read_account",
+        CommandSchema {
+            schema: "v2/GetAccountParams",
+            field: None,
+            request_id: false,
+        },
+    ),
+    (
+        "login_account",
+        CommandSchema {
+            schema: "v2/LoginAccountParams",
+            field: Some("params"),
+            request_id: false,
+        },
+    ),
+    (
+        "cancel_account_login",
+        CommandSchema {
+            schema: "v2/CancelLoginAccountParams",
+            field: None,
+            request_id: false,
+        },
+    ),
+    (
+        "set_disabled_tools",
+        CommandSchema {
+            schema: "host/SetDisabledToolsConfig",
+            field: None,
+            request_id: false,
+        },
+    ),
+];
+
+static SCHEMAS: LazyLock<HashMap<&'static str, Value>> = LazyLock::new(|| {
+    let sources = [
+        ("RequestId", protocol_schema!("RequestId.json")),
+        (
+            "CommandExecutionRequestApprovalResponse",
+            protocol_schema!("CommandExecutionRequestApprovalResponse.json"),
+        ),
+        (
+            "FileChangeRequestApprovalResponse",
+            protocol_schema!("FileChangeRequestApprovalResponse.json"),
+        ),
+        ("ToolRequestUserInputResponse", protocol_schema!("ToolRequestUserInputResponse.json")),
+        ("ChatgptAuthTokensRefreshResponse", protocol_schema!("ChatgptAuthTokensRefreshResponse.json")),
+        ("v2/GetAccountParams", protocol_schema!("v2/GetAccountParams.json")),
+        ("v2/LoginAccountParams", protocol_schema!("v2/LoginAccountParams.json")),
+        ("v2/CancelLoginAccountParams", protocol_schema!("v2/CancelLoginAccountParams.json")),
+        ("host/SetDisabledToolsConfig", host_schema!("SetDisabledToolsConfig.json")),
+    ];
+    sources
+        .into_iter()
+        .map(|(name, source)| {
+            let schema = serde_json::from_str(source)
+                .unwrap_or_else(|e| panic!("protocol schema {name} is not valid JSON: {e}"));
+            (name, schema)
+        })
+        .collect()
+});
+
+// Names the command whose schema a typed config is validated against.
+pub trait CommandPayload {
+    const TAURI_COMMAND: &'static str;
+}
+
+// A typed command argument that is checked against its schema before serde
+// sees it, so a bad payload gets the field-level message rather than serde's
+// first complaint. Serializes as the inner value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validated<T>(pub T);
+
+impl<'de, T: CommandPayload + DeserializeOwned> Deserialize<'de> for Validated<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = Value::deserialize(deserializer)?;
+        validate_command_payload(T::TAURI_COMMAND, &raw).map_err(D::Error::custom)?;
+        serde_json::from_value(raw)
+            .map(Validated)
+            .map_err(|e| D::Error::custom(format!("invalid {} payload: {e}", T::TAURI_COMMAND)))
+    }
+}
+
+impl<T: Serialize> Serialize for Validated<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+// The frontend sends the inner payload, so the bindings name the inner type.
+#[cfg(test)]
+impl<T: ts_rs::TS> ts_rs::TS for Validated<T> {
+    type WithoutGenerics = T::WithoutGenerics;
+    type OptionInnerType = Self;
+
+    fn ident() -> String {
+        T::ident()
+    }
+
+    fn name() -> String {
+        T::name()
+    }
+
+    fn inline() -> String {
+        T::inline()
+    }
+
+    fn inline_flattened() -> String {
+        T::inline_flattened()
+    }
+
+    fn decl() -> String {
+        T::decl()
+    }
+
+    fn decl_concrete() -> String {
+        T::decl_concrete()
+    }
+
+    fn visit_dependencies(visitor: &mut impl ts_rs::TypeVisitor)
+    where
+        Self: 'static,
+    {
+        T::visit_dependencies(visitor);
+    }
+
+    fn visit_generics(visitor: &mut impl ts_rs::TypeVisitor)
+    where
+        Self: 'static,
+    {
+        T::visit_generics(visitor);
+        visitor.visit::<T>();
+    }
+}
+
+pub fn validate_command_payload(tauri_command: &str, payload: &Value) -> Result<(), String> {
+    let Some((_, command)) = COMMAND_SCHEMAS.iter().find(|(name, _)| *name == tauri_command) else {
+        return Ok(());
+    };
+
+    let mut errors = Vec::new();
+    let root_path = "config".to_string();
+    if !payload.is_object() {
+        errors.push(format!("{root_path}: expected object, found {}", type_name(payload)));
+    } else {
+        if command.request_id {
+            match payload.get("requestId") {
+                Some(request_id) => {
+                    let schema = &SCHEMAS["RequestId"];
+                    validate(schema, schema, request_id, &format!("{root_path}.requestId"), &mut errors);
+                }
+                None => errors.push(format!("{root_path}.requestId: is required")),
+            }
+        }
+        let schema = &SCHEMAS[command.schema];
+        match command.field {
+            Some(field) => match payload.get(field) {
+                Some(value) => validate(schema, schema, value, &format!("{root_path}.{field}"), &mut errors),
+                None => errors.push(format!("{root_path}.{field}: is required")),
+            },
+            None => validate(schema, schema, payload, &root_path, &mut errors),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("invalid {tauri_command} payload: {}", errors.join("; ")))
+    }
+}
+
+// Covers the draft-07 keywords used by the generated protocol schemas; annotation
+// keywords (`title`, `description`, `default`, `format`) are ignored.
+fn validate(schema: &Value, root: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            errors.push(format!("{path}: is not allowed"));
+            return;
+        }
+        Value::Object(_) => schema,
+        _ => return,
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        match resolve_ref(root, reference) {
+            Some(target) => validate(target, root, value, path, errors),
+            None => errors.push(format!("{path}: unresolvable schema reference {reference}")),
+        }
+        return;
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|name| type_matches(name, value)) {
+            errors.push(format!(
+                "{path}: expected {}, found {}",
+                allowed.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            let options: Vec<String> = options.iter().map(Value::to_string).collect();
+            errors.push(format!("{path}: must be one of {}", options.join(", ")));
+        }
+    }
+    if let Some(constant) = schema.get("const") {
+        if constant != value {
+            errors.push(format!("{path}: must be {constant}"));
+        }
+    }
+
+    if let Some(variants) = schema.get("allOf").and_then(Value::as_array) {
+        for variant in variants {
+            validate(variant, root, value, path, errors);
+        }
+    }
+    if let Some(variants) = schema.get("anyOf").and_then(Value::as_array) {
+        validate_variants(variants, root, value, path, errors, false);
+    }
+    if let Some(variants) = schema.get("oneOf").and_then(Value::as_array) {
+        validate_variants(variants, root, value, path, errors, true);
+    }
+
+    if let Value::Object(fields) = value {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !fields.contains_key(key) {
+                    errors.push(format!("{path}.{key}: is required"));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (key, field_value) in fields {
+            let field_path = format!("{path}.{key}");
+            match properties.and_then(|properties| properties.get(key)) {
+                Some(field_schema) => validate(field_schema, root, field_value, &field_path, errors),
+                None => {
+                    if let Some(additional) = schema.get("additionalProperties") {
+                        validate(additional, root, field_value, &field_path, errors);
+                    }
+                }
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            validate(item_schema, root, item, &format!("{path}[{index}]"), errors);
+        }
+    }
+}
+
+fn validate_variants(
+    variants: &[Value],
+    root: &Value,
+    value: &Value,
+    path: &str,
+    errors: &mut Vec<String>,
+    exactly_one: bool,
+) {
+    let results: Vec<Vec<String>> = variants
+        .iter()
+        .map(|variant| {
+            let mut variant_errors = Vec::new();
+            validate(variant, root, value, path, &mut variant_errors);
+            variant_errors
+        })
+        .collect();
+    let matched = results.iter().filter(|variant_errors| variant_errors.is_empty()).count();
+    if matched == 1 || (matched > 1 && !exactly_one) {
+        return;
+    }
+    if matched > 1 {
+        errors.push(format!("{path}: matches more than one allowed variant"));
+        return;
+    }
+
+    // When the value's type or tag selects a single variant, report that
+    // variant's field errors instead of a generic "no variant matched".
+    let candidates: Vec<usize> = (0..variants.len())
+        .filter(|index| !discriminated_out(&variants[*index], root, value))
+        .collect();
+    if let [only] = candidates.as_slice() {
+        errors.extend(results[*only].iter().cloned());
+        return;
+    }
+    let described: Vec<String> = variants
+        .iter()
+        .map(|variant| describe_variant(variant, root))
+        .collect();
+    errors.push(format!("{path}: must match one of {}", described.join(", ")));
+}
+
+fn discriminated_out(variant: &Value, root: &Value, value: &Value) -> bool {
+    let variant = resolve_schema(variant, root);
+    if let Some(tag) = single_value(variant) {
+        return tag != value;
+    }
+    if let Some(expected) = variant.get("type").and_then(Value::as_str) {
+        if !type_matches(expected, value) {
+            return true;
+        }
+    }
+    let (Some(properties), Value::Object(fields)) =
+        (variant.get("properties").and_then(Value::as_object), value)
+    else {
+        return false;
+    };
+    properties.iter().any(|(key, property)| {
+        match (single_value(resolve_schema(property, root)), fields.get(key)) {
+            (Some(tag), Some(actual)) => tag != actual,
+            _ => false,
+        }
+    })
+}
+
+fn describe_variant(variant: &Value, root: &Value) -> String {
+    let variant = resolve_schema(variant, root);
+    if let Some(tag) = single_value(variant) {
+        return tag.to_string();
+    }
+    if let Some(properties) = variant.get("properties").and_then(Value::as_object) {
+        if let Some((key, tag)) = properties
+            .iter()
+            .find_map(|(key, property)| single_value(resolve_schema(property, root)).map(|tag| (key, tag)))
+        {
+            return format!("{{\"{key}\": {tag}}}");
+        }
+    }
+    if let Some(required) = variant.get("required").and_then(Value::as_array) {
+        let keys: Vec<&str> = required.iter().filter_map(Value::as_str).collect();
+        if !keys.is_empty() {
+            return format!("object with {}", keys.join(", "));
+        }
+    }
+    variant
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or("value")
+        .to_string()
+}
+
+fn single_value(schema: &Value) -> Option<&Value> {
+    if let Some(constant) = schema.get("const") {
+        return Some(constant);
+    }
+    match schema.get("enum").and_then(Value::as_array) {
+        Some(options) if options.len() == 1 => options.first(),
+        _ => None,
+    }
+}
+
+fn resolve_schema<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| resolve_ref(root, reference))
+        .unwrap_or(schema)
+}
+
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    root.pointer(reference.strip_prefix('#')?)
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol_types::{LoginAccountConfig, SetDisabledToolsConfig};
+    use serde_json::json;
+
+    fn rejected(tauri_command: &str, payload: Value) -> String {
+        validate_command_payload(tauri_command, &payload).unwrap_err()
+    }
+
+    #[test]
+    fn missing_required_fields_name_their_path() {
+        assert_eq!(
+            rejected("respond_command_approval", json!({ "decision": "accept" })),
+            "invalid respond_command_approval payload: config.requestId: is required"
+        );
+        assert_eq!(
+            rejected("login_account", json!({ "params": { "type": "apiKey" } })),
+            "invalid login_account payload: config.params.apiKey: is required"
+        );
+    }
+
+    #[test]
+    fn type_mismatches_name_the_expected_and_found_types() {
+        assert_eq!(
+            rejected("set_disabled_tools", json!({ "tools": "shell" })),
+            "invalid set_disabled_tools payload: config.tools: expected array, found string"
+        );
+        assert_eq!(
+            rejected("set_disabled_tools", json!({ "tools": ["shell", 2] })),
+            "invalid set_disabled_tools payload: config.tools[1]: expected string, found number"
+        );
+        assert_eq!(
+            rejected("respond_tool_user_input", json!({ "requestId": 1, "answers": { "q1": { "answers": "yes" } } })),
+            "invalid respond_tool_user_input payload: config.answers.q1.answers: expected array, found string"
+        );
+    }
+
+    #[test]
+    fn enum_values_outside_the_list_are_rejected() {
+        let mut errors = Vec::new();
+        let schema = json!({ "enum": ["plus", "pro"] });
+        validate(&schema, &schema, &json!("team"), "config.plan", &mut errors);
+        assert_eq!(errors, vec!["config.plan: must be one of \"plus\", \"pro\""]);
+    }
+
+    #[test]
+    fn one_of_reports_the_tagged_variant_or_lists_the_alternatives() {
+        assert_eq!(
+            rejected("login_account", json!({ "params": { "type": "apiKey", "apiKey": 3 } })),
+            "invalid login_account payload: config.params.apiKey: expected string, found number"
+        );
+        assert_eq!(
+            rejected("login_account", json!({ "params": { "type": "bogus" } })),
+            "invalid login_account payload: config.params: must match one of {\"type\": \"apiKey\"}, {\"type\": \"chatgpt\"}, {\"type\": \"chatgptAuthTokens\"}"
+        );
+        assert_eq!(
+            rejected("respond_file_change_approval", json!({ "requestId": 1, "decision": "maybe" })),
+            "invalid respond_file_change_approval payload: config.decision: must match one of \"accept\", \"acceptForSession\", \"decline\", \"cancel\""
+        );
+    }
+
+    #[test]
+    fn typed_arguments_are_validated_before_they_deserialize() {
+        let error = serde_json::from_value::<Validated<LoginAccountConfig>>(json!({ "params": { "type": "apiKey" } }))
+            .unwrap_err();
+        assert_eq!(error.to_string(), "invalid login_account payload: config.params.apiKey: is required");
+
+        let config =
+            serde_json::from_value::<Validated<LoginAccountConfig>>(json!({ "params": { "type": "chatgpt" } })).unwrap();
+        assert_eq!(serde_json::to_value(&config).unwrap(), json!({ "params": { "type": "chatgpt" } }));
+    }
+
+    #[test]
+    fn disabled_tools_keep_their_schema_messages_as_a_typed_argument() {
+        let error = serde_json::from_value::<Validated<SetDisabledToolsConfig>>(json!({ "tools": "shell" })).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid set_disabled_tools payload: config.tools: expected array, found string"
+        );
+
+        let config = serde_json::from_value::<Validated<SetDisabledToolsConfig>>(json!({ "tools": ["shell"] })).unwrap();
+        assert_eq!(config.0.tools, vec!["shell".to_string()]);
+        assert_eq!(serde_json::to_value(&config).unwrap(), json!({ "tools": ["shell"] }));
+    }
+}
@@ -0,0 +1,286 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tokio::process::{Child, Command};
+use tokio::time::{timeout, Duration};
+
+const PID_FILE_NAME: &str = "helper-pids.jsonl";
+// Separate from the pid file, which is replaced and removed while locked.
+const PID_LOCK_FILE_NAME: &str = "helper-pids.lock";
+pub const TERMINATE_GRACE: Duration = Duration::from_millis(1500);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HelperPidRecord {
+    pid: u32,
+    // Kernel start time of the process; guards against signalling a recycled pid.
+    start_time: Option<u64>,
+}
+
+// The helper becomes the leader of its own process group so teardown can reach
+// `codex app-server` and anything it spawned. On Linux it also receives SIGTERM
+// if the host dies without running its exit hooks; spawn it with `spawn` below.
+pub fn isolate_process_tree(command: &mut Command) {
+    #[cfg(unix)]
+    command.process_group(0);
+    #[cfg(target_os = "linux")]
+    {
+        let parent = std::process::id() as libc::pid_t;
+        // SAFETY: runs in the forked child before exec and only calls
+        // async-signal-safe libc functions.
+        unsafe {
+            command.pre_exec(move || {
+                if libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGTERM as libc::c_ulong, 0, 0, 0) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                // The host may have died between fork and prctl.
+                if libc::getppid() != parent {
+                    libc::_exit(1);
+                }
+                Ok(())
+            });
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = command;
+}
+
+// PR_SET_PDEATHSIG fires when the thread that forked the child exits, not the
+// process, and runtime threads come and go (tokio retires idle blocking-pool
+// threads). Helpers are therefore forked from one thread that lives as long as
+// the host.
+#[cfg(target_os = "linux")]
+pub async fn spawn(mut command: Command) -> std::io::Result<Child> {
+    use std::sync::mpsc;
+    use std::sync::OnceLock;
+
+    type SpawnJob = Box<dyn FnOnce() + Send>;
+    static SPAWNER: OnceLock<mpsc::Sender<SpawnJob>> = OnceLock::new();
+    let spawner = SPAWNER.get_or_init(|| {
+        let (sender, jobs) = mpsc::channel::<SpawnJob>();
+        std::thread::Builder::new()
+            .name("helper-spawner".to_string())
+            .spawn(move || jobs.into_iter().for_each(|job| job()))
+            .expect("failed to start the helper spawner thread");
+        sender
+    });
+    let gone = || std::io::Error::other("helper spawner thread is gone");
+    let runtime = tokio::runtime::Handle::current();
+    let (reply, spawned) = tokio::sync::oneshot::channel();
+    spawner
+        .send(Box::new(move || {
+            // Child reaping is driven by the runtime the child is spawned in.
+            let _runtime = runtime.enter();
+            let _ = reply.send(command.spawn());
+        }))
+        .map_err(|_| gone())?;
+    spawned.await.map_err(|_| gone())?
+}
+
+#[cfg(not(target_os = "linux"))]
+pub async fn spawn(mut command: Command) -> std::io::Result<Child> {
+    command.spawn()
+}
+
+// SIGTERM to the whole group lets the helper run its graceful shutdown; whatever
+// is still alive after `grace` is SIGKILLed.
+pub async fn terminate_process_tree(child: &mut Child, pid: Option<u32>, grace: Duration) {
+    #[cfg(unix)]
+    if let Some(pid) = pid {
+        signal_group(pid, libc::SIGTERM);
+        let _ = timeout(grace, child.wait()).await;
+        signal_group(pid, libc::SIGKILL);
+        let _ = timeout(Duration::from_millis(500), child.wait()).await;
+        return;
+    }
+    let _ = (pid, grace);
+    let _ = child.kill().await;
+    let _ = timeout(Duration::from_millis(500), child.wait()).await;
+}
+
+#[cfg(unix)]
+fn signal_group(pgid: u32, signal: libc::c_int) -> bool {
+    // SAFETY: plain syscall; a negative pid addresses the process group.
+    unsafe { libc::kill(-(pgid as libc::pid_t), signal) == 0 }
+}
+
+pub struct HelperPidFile {
+    path: PathBuf,
+}
+
+impl HelperPidFile {
+    pub fn for_app(app: &AppHandle) -> Result<Self, String> {
+        let data_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("failed to resolve app data dir for helper pid file: {e}"))?;
+        Ok(Self {
+            path: data_dir.join(PID_FILE_NAME),
+        })
+    }
+
+    pub fn record(&self, pid: u32) -> Result<(), String> {
+        let _lock = self.lock()?;
+        let mut records = self.read();
+        records.retain(|record| record.pid != pid);
+        records.push(HelperPidRecord {
+            pid,
+            start_time: process_start_time(pid),
+        });
+        self.write(&records)
+    }
+
+    pub fn remove(&self, pid: u32) -> Result<(), String> {
+        let _lock = self.lock()?;
+        let mut records = self.read();
+        let before = records.len();
+        records.retain(|record| record.pid != pid);
+        if records.len() == before {
+            return Ok(());
+        }
+        self.write(&records)
+    }
+
+    // Reaps helpers left behind by a previous host that was killed before its
+    // exit hooks ran. Returns the process group ids that were signalled.
+    pub fn sweep_stale(&self) -> Result<Vec<u32>, String> {
+        let _lock = self.lock()?;
+        let records = self.read();
+        if records.is_empty() {
+            return Ok(Vec::new());
+        }
+        let reaped: Vec<u32> = records
+            .iter()
+            .filter(|record| is_stale_helper(record))
+            .map(|record| record.pid)
+            .collect();
+        #[cfg(unix)]
+        if !reaped.is_empty() {
+            for pid in &reaped {
+                signal_group(*pid, libc::SIGTERM);
+            }
+            let deadline = std::time::Instant::now() + TERMINATE_GRACE;
+            while std::time::Instant::now() < deadline
+                && reaped.iter().any(|pid| signal_group(*pid, 0))
+            {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+            }
+            for pid in &reaped {
+                signal_group(*pid, libc::SIGKILL);
+            }
+        }
+        fs::remove_file(&self.path)
+            .map_err(|e| format!("failed to clear helper pid file {}: {e}", self.path.display()))?;
+        Ok(reaped)
+    }
+
+    // Sessions record and remove helpers concurrently; every read-modify-write
+    // of the pid file holds this lock until it returns.
+    fn lock(&self) -> Result<File, String> {
+        let path = self.path.with_file_name(PID_LOCK_FILE_NAME);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create helper pid dir {}: {e}", parent.display()))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| format!("failed to open helper pid lock {}: {e}", path.display()))?;
+        file.lock()
+            .map_err(|e| format!("failed to lock helper pid lock {}: {e}", path.display()))?;
+        Ok(file)
+    }
+
+    fn read(&self) -> Vec<HelperPidRecord> {
+        fs::read_to_string(&self.path)
+            .map(|source| {
+                source
+                    .lines()
+                    .filter_map(|line| serde_json::from_str(line).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn write(&self, records: &[HelperPidRecord]) -> Result<(), String> {
+        if records.is_empty() {
+            return match fs::remove_file(&self.path) {
+                Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(format!(
+                    "failed to clear helper pid file {}: {error}",
+                    self.path.display()
+                )),
+                _ => Ok(()),
+            };
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create helper pid dir {}: {e}", parent.display()))?;
+        }
+        let mut contents = String::new();
+        for record in records {
+            let line = serde_json::to_string(record).map_err(|e| format!("failed to encode helper pid: {e}"))?;
+            contents.push_str(&line);
+            contents.push('\n');
+        }
+        fs::write(&self.path, contents)
+            .map_err(|e| format!("failed to write helper pid file {}: {e}", self.path.display()))
+    }
+}
+
+// A recorded leader that is still running must be the same process (same start
+// time). A leader that is gone can still leave group members behind; Linux does
+// not recycle a pid while it names a live process group, so signalling it is safe.
+#[cfg(target_os = "linux")]
+fn is_stale_helper(record: &HelperPidRecord) -> bool {
+    match (process_start_time(record.pid), record.start_time) {
+        (Some(current), Some(recorded)) => current == recorded,
+        (Some(_), None) => false,
+        (None, _) => signal_group(record.pid, 0),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_stale_helper(_record: &HelperPidRecord) -> bool {
+    false
+}
+
+#[cfg(target_os = "linux")]
+fn process_start_time(pid: u32) -> Option<u64> {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // Fields after the parenthesised command name; start time is field 22 overall.
+    let rest = &stat[stat.rfind(')')? + 1..];
+    rest.split_whitespace().nth(19)?.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_start_time(_pid: u32) -> Option<u64> {
+    None
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn helpers_outlive_the_thread_that_asked_for_them() {
+        let runtime = tokio::runtime::Handle::current();
+        let mut child = std::thread::spawn(move || {
+            runtime.block_on(async {
+                let mut command = Command::new("sleep");
+                command.arg("5");
+                isolate_process_tree(&mut command);
+                spawn(command).await.expect("sleep spawns")
+            })
+        })
+        .join()
+        .expect("requesting thread finishes");
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(child.try_wait().expect("child status").is_none());
+        let pid = child.id();
+        terminate_process_tree(&mut child, pid, Duration::from_millis(100)).await;
+    }
+}
@@ -0,0 +1,153 @@
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+
+use crate::payload_schema::CommandPayload;
+
+// Generated by build.rs from the component package's protocol schemas, so the
+// typed command arguments cannot drift from what payload_schema validates.
+include!(concat!(env!("OUT_DIR"), "/protocol_types.rs"));
+
+// Server-request responses carry the JSON-RPC id next to the response body.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct ServerRequestResponse<T> {
+    pub request_id: RequestId,
+    #[serde(flatten)]
+    pub response: T,
+}
+
+// serde's flatten hands the body only the fields it names, so unknown fields
+// would vanish; the body gets everything but the id and rejects them itself.
+impl<'de, T: DeserializeOwned> Deserialize<'de> for ServerRequestResponse<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut body = Map::<String, Value>::deserialize(deserializer)?;
+        let request_id = body.remove("requestId").ok_or_else(|| D::Error::missing_field("requestId"))?;
+        Ok(Self {
+            request_id: serde_json::from_value(request_id).map_err(D::Error::custom)?,
+            response: serde_json::from_value(Value::Object(body)).map_err(D::Error::custom)?,
+        })
+    }
+}
+
+pub type RespondCommandApprovalConfig = ServerRequestResponse<CommandExecutionRequestApprovalResponse>;
+pub type RespondFileChangeApprovalConfig = ServerRequestResponse<FileChangeRequestApprovalResponse>;
+pub type RespondToolUserInputConfig = ServerRequestResponse<ToolRequestUserInputResponse>;
+pub type RespondChatgptAuthTokensRefreshConfig = ServerRequestResponse<ChatgptAuthTokensRefreshResponse>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(deny_unknown_fields)]
+pub struct LoginAccountConfig {
+    pub params: LoginAccountParams,
+}
+
+macro_rules! command_payloads {
+    ($($config:ty => $command:literal),* $(,)?) => {
+        $(impl CommandPayload for $config {
+            const TAURI_COMMAND: &'static str = $command;
+        })*
+    };
+}
+
+command_payloads! {
+    RespondCommandApprovalConfig => "respond_command_approval",
+    RespondFileChangeApprovalConfig => "respond_file_change_approval",
+    RespondToolUserInputConfig => "respond_tool_user_input",
+    RespondChatgptAuthTokensRefreshConfig => "respond_chatgpt_auth_tokens_refresh",
+    GetAccountParams => "read_account",
+    LoginAccountConfig => "login_account",
+    CancelLoginAccountParams => "cancel_account_login",
+    SetDisabledToolsConfig => "set_disabled_tools",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payload_schema::validate_command_payload;
+    use serde_json::json;
+
+    // Forwarding re-serializes the typed config, so the helper must see the same JSON
+    // the frontend sent, and it must still pass schema validation.
+    fn round_trip<T: DeserializeOwned + Serialize>(tauri_command: &str, payload: Value) -> T {
+        let typed: T = serde_json::from_value(payload.clone()).expect("payload deserializes");
+        let forwarded = serde_json::to_value(&typed).expect("payload serializes");
+        assert_eq!(forwarded, payload);
+        validate_command_payload(tauri_command, &forwarded).expect("forwarded payload is schema-valid");
+        typed
+    }
+
+    #[test]
+    fn approval_decisions_round_trip() {
+        let config: RespondCommandApprovalConfig = round_trip(
+            "respond_command_approval",
+            json!({ "requestId": 7, "decision": "acceptForSession" }),
+        );
+        assert_eq!(config.request_id, RequestId::Integer(7));
+        assert_eq!(config.response.decision, CommandExecutionApprovalDecision::AcceptForSession);
+
+        let config: RespondCommandApprovalConfig = round_trip(
+            "respond_command_approval",
+            json!({
+                "requestId": "req-1",
+                "decision": { "acceptWithExecpolicyAmendment": { "execpolicy_amendment": ["git", "status"] } },
+            }),
+        );
+        assert_eq!(
+            config.response.decision,
+            CommandExecutionApprovalDecision::AcceptWithExecpolicyAmendment {
+                execpolicy_amendment: vec!["git".to_string(), "status".to_string()],
+            }
+        );
+
+        round_trip::<RespondFileChangeApprovalConfig>(
+            "respond_file_change_approval",
+            json!({ "requestId": 3, "decision": "decline" }),
+        );
+        let invalid = json!({ "requestId": 3, "decision": "acceptWithExecpolicyAmendment" });
+        assert!(serde_json::from_value::<RespondFileChangeApprovalConfig>(invalid).is_err());
+    }
+
+    #[test]
+    fn tool_input_and_token_refresh_round_trip() {
+        let config: RespondToolUserInputConfig = round_trip(
+            "respond_tool_user_input",
+            json!({ "requestId": 9, "answers": { "q1": { "answers": ["yes"] } } }),
+        );
+        assert_eq!(config.response.answers["q1"].answers, vec!["yes".to_string()]);
+
+        round_trip::<RespondChatgptAuthTokensRefreshConfig>(
+            "respond_chatgpt_auth_tokens_refresh",
+            json!({ "requestId": 4, "accessToken": "token", "chatgptAccountId": "acct-1", "chatgptPlanType": "plus" }),
+        );
+    }
+
+    #[test]
+    fn account_params_round_trip() {
+        let config: LoginAccountConfig = round_trip(
+            "login_account",
+            json!({ "params": { "type": "apiKey", "apiKey": "sk-test" } }),
+        );
+        assert_eq!(config.params, LoginAccountParams::ApiKey { api_key: "sk-test".to_string() });
+        round_trip::<LoginAccountConfig>("login_account", json!({ "params": { "type": "chatgpt" } }));
+
+        let read: GetAccountParams = round_trip("read_account", json!({}));
+        assert_eq!(read.refresh_token, None);
+        round_trip::<CancelLoginAccountParams>("cancel_account_login", json!({ "loginId": "login-1" }));
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected_rather_than_dropped() {
+        let reject = |error: serde_json::Error| error.to_string().starts_with("unknown field `bogus`");
+        assert!(serde_json::from_value::<GetAccountParams>(json!({ "bogus": true })).is_err_and(reject));
+        assert!(serde_json::from_value::<LoginAccountConfig>(json!({ "params": { "type": "chatgpt" }, "bogus": 1 }))
+            .is_err_and(reject));
+        assert!(serde_json::from_value::<LoginAccountConfig>(json!({ "params": { "type": "apiKey", "apiKey": "sk-test", "bogus": 1 } }))
+            .is_err_and(reject));
+        assert!(
+            serde_json::from_value::<RespondCommandApprovalConfig>(json!({ "requestId": 7, "decision": "accept", "bogus": 1 }))
+                .is_err_and(reject)
+        );
+    }
+}
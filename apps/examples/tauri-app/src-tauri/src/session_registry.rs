@@ -0,0 +1,188 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::AppHandle;
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+
+use crate::bridge_process::{BridgeRuntime, BridgeStateSnapshot};
+use crate::helper_shutdown::{ShutdownConfig, StopReport};
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub running: bool,
+    pub phase: Option<String>,
+    pub cwd: Option<String>,
+    pub local_thread_id: Option<String>,
+    pub turn_id: Option<String>,
+    pub last_error_code: Option<String>,
+}
+
+// One helper per session id. Commands that omit `sessionId` target the only
+// session when exactly one exists. A session stays registered only while a start
+// succeeded or left a helper process behind.
+#[derive(Default)]
+pub struct AppBridgeState {
+    sessions: Mutex<HashMap<String, BridgeRuntime>>,
+}
+
+impl AppBridgeState {
+    pub async fn runtime_for_start(&self, session_id: &str) -> BridgeRuntime {
+        let mut sessions = self.sessions.lock().await;
+        sessions
+            .entry(session_id.to_string())
+            .or_insert_with(|| BridgeRuntime::for_session(session_id))
+            .clone()
+    }
+
+    pub async fn runtime(&self, session_id: Option<&str>) -> Result<BridgeRuntime, String> {
+        let sessions = self.sessions.lock().await;
+        match session_id {
+            Some(session_id) => sessions
+                .get(session_id)
+                .cloned()
+                .ok_or_else(|| format!("[E_TAURI_SESSION_NOT_FOUND] no bridge session with id \"{session_id}\"")),
+            None => match sessions.len() {
+                0 => Err("[E_TAURI_SESSION_NONE] no bridge session is running; call start_bridge first".to_string()),
+                1 => Ok(sessions.values().next().cloned().expect("one session")),
+                count => Err(format!(
+                    "[E_TAURI_SESSION_AMBIGUOUS] {count} bridge sessions are running; pass sessionId"
+                )),
+            },
+        }
+    }
+
+    // A failed start that left no helper behind drops the session again, so it
+    // does not make later calls without `sessionId` ambiguous.
+    pub async fn discard_if_idle(&self, session_id: &str) {
+        let mut sessions = self.sessions.lock().await;
+        let Some(runtime) = sessions.get(session_id) else {
+            return;
+        };
+        if !runtime.has_process().await {
+            sessions.remove(session_id);
+        }
+    }
+
+    // Before the first start the bridge reads as stopped rather than failing.
+    pub async fn snapshot(&self, session_id: Option<&str>) -> Result<BridgeStateSnapshot, String> {
+        if session_id.is_none() && self.sessions.lock().await.is_empty() {
+            return Ok(BridgeStateSnapshot::default());
+        }
+        Ok(self.runtime(session_id).await?.snapshot().await)
+    }
+
+    // Entry point for the generated forwarding commands in `bridge_commands_generated.rs`.
+    pub async fn forward_bridge_command(
+        &self,
+        app: AppHandle,
+        session_id: Option<&str>,
+        tauri_command: &str,
+        payload: serde_json::Value,
+    ) -> Result<(), String> {
+        self.runtime(session_id)
+            .await?
+            .forward_tauri_json_command(app, tauri_command, payload)
+            .await
+    }
+
+    pub async fn stop_session(
+        &self,
+        app: AppHandle,
+        session_id: Option<&str>,
+        config: ShutdownConfig,
+    ) -> Result<StopReport, String> {
+        // Stopping with nothing started is a no-op, as it is for a stopped session.
+        if session_id.is_none() && self.sessions.lock().await.is_empty() {
+            return Ok(StopReport::default());
+        }
+        let runtime = self.runtime(session_id).await?;
+        let report = runtime.stop(app, config).await?;
+        self.sessions.lock().await.remove(runtime.session_id());
+        Ok(report)
+    }
+
+    pub async fn stop_all(&self, app: &AppHandle, config: ShutdownConfig) {
+        let runtimes: Vec<BridgeRuntime> = self.sessions.lock().await.drain().map(|(_, runtime)| runtime).collect();
+        let mut stops = JoinSet::new();
+        for runtime in runtimes {
+            let app = app.clone();
+            stops.spawn(async move { runtime.stop(app, config).await });
+        }
+        while stops.join_next().await.is_some() {}
+    }
+
+    pub async fn snapshots(&self) -> Vec<(String, BridgeStateSnapshot)> {
+        let runtimes: Vec<BridgeRuntime> = self.sessions.lock().await.values().cloned().collect();
+        let mut snapshots = Vec::with_capacity(runtimes.len());
+        for runtime in runtimes {
+            snapshots.push((runtime.session_id().to_string(), runtime.snapshot().await));
+        }
+        snapshots
+    }
+
+    pub async fn list(&self) -> Vec<SessionSummary> {
+        let runtimes: Vec<BridgeRuntime> = self.sessions.lock().await.values().cloned().collect();
+        let mut summaries = Vec::with_capacity(runtimes.len());
+        for runtime in runtimes {
+            let snapshot = runtime.snapshot().await;
+            summaries.push(SessionSummary {
+                session_id: runtime.session_id().to_string(),
+                running: snapshot.running,
+                phase: snapshot.phase,
+                cwd: runtime.start_cwd().await,
+                local_thread_id: snapshot.local_thread_id,
+                turn_id: snapshot.turn_id,
+                last_error_code: snapshot.last_error_code,
+            });
+        }
+        summaries.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+        summaries
+    }
+}
+
+// `start_bridge` names its session twice: the `sessionId` argument every bridge
+// command takes, and `config.sessionId`, which the helper runs under. A mismatch
+// would key the helper under one id while it records threads under the other.
+pub fn start_session_id<'a>(session_id: Option<&'a str>, config_session_id: &'a str) -> Result<&'a str, String> {
+    match session_id {
+        Some(session_id) if session_id != config_session_id => Err(format!(
+            "[E_TAURI_SESSION_MISMATCH] sessionId \"{session_id}\" does not match config.sessionId \"{config_session_id}\""
+        )),
+        _ => Ok(config_session_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_session_id_must_match_the_config() {
+        assert_eq!(start_session_id(None, "repo-a"), Ok("repo-a"));
+        assert_eq!(start_session_id(Some("repo-a"), "repo-a"), Ok("repo-a"));
+        let error = start_session_id(Some("repo-b"), "repo-a").unwrap_err();
+        assert!(error.starts_with("[E_TAURI_SESSION_MISMATCH]"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn calls_without_a_session_fail_explicitly() {
+        let state = AppBridgeState::default();
+        let error = state.runtime(None).await.err().unwrap();
+        assert!(error.starts_with("[E_TAURI_SESSION_NONE]"), "{error}");
+        assert!(!state.snapshot(None).await.unwrap().running);
+    }
+
+    #[tokio::test]
+    async fn a_failed_start_does_not_leave_the_session_registered() {
+        let state = AppBridgeState::default();
+        state.runtime_for_start("repo-a").await;
+        state.runtime_for_start("repo-b").await;
+        assert!(state.runtime(None).await.err().unwrap().starts_with("[E_TAURI_SESSION_AMBIGUOUS]"));
+
+        state.discard_if_idle("repo-b").await;
+        assert_eq!(state.runtime(None).await.unwrap().session_id(), "repo-a");
+    }
+}
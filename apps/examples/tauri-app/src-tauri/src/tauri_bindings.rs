@@ -0,0 +1,515 @@
+// TypeScript bindings for the frontend, checked in as ../src/lib/tauriBindings.generated.ts.
+// Types come from their ts-rs derives; COMMANDS lists each #[tauri::command]'s
+// arguments and result, and a test holds it to the signatures in main.rs and
+// bridge_commands_generated.rs. `pnpm run tauri:bindings` rewrites the file, and
+// `pnpm run check:tauri-bindings` (run by CI) fails while it is stale.
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use ts_rs::{Dependency, TypeVisitor, TS};
+
+use crate::bridge_contract_generated::BRIDGE_COMMANDS;
+use crate::helper_readiness::StartStage;
+use crate::host_commands::HOST_COMMANDS;
+use crate::host_events::HOST_EVENTS;
+use crate::payload_schema::Validated;
+use crate::protocol_types::{
+    CancelLoginAccountParams, ChatgptAuthTokensRefreshResponse, CommandExecutionApprovalDecision,
+    CommandExecutionRequestApprovalResponse, FileChangeApprovalDecision, FileChangeRequestApprovalResponse,
+    GetAccountParams, LoginAccountConfig, LoginAccountParams, RequestId, RespondChatgptAuthTokensRefreshConfig,
+    RespondCommandApprovalConfig, RespondFileChangeApprovalConfig, RespondToolUserInputConfig, ServerRequestResponse,
+    SetDisabledToolsConfig, ToolRequestUserInputAnswer, ToolRequestUserInputResponse,
+};
+use crate::{
+    audit_verification, bridge_doctor, bridge_process, diagnostics_bundle, event_routing, helper_process_policy,
+    helper_resolution, helper_shutdown, instance_lock, session_registry, OpenThreadConfig, StartBridgeConfig,
+};
+
+const BINDINGS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../src/lib/tauriBindings.generated.ts");
+const BINDINGS_WRITE_ENV: &str = "CODEX_TAURI_BINDINGS_WRITE";
+
+const MAIN_SOURCE: &str = include_str!("main.rs");
+const FORWARD_COMMANDS_SOURCE: &str = include_str!("bridge_commands_generated.rs");
+
+// Arguments Tauri fills in itself; the frontend never passes them.
+const INJECTED_ARGUMENTS: &[&str] = &["tauri::AppHandle", "tauri::State<", "State<", "tauri::WebviewWindow", "tauri::Window"];
+
+// Payload shapes of the events in src/host_events.rs. The host builds these
+// payloads as JSON or forwards them from the helper, so the types only describe
+// them. `sessionId` is added to every payload of a session's runtime; nullable
+// fields of a state update are null when cleared and absent when unchanged.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(optional_fields)]
+struct BridgeStateEvent {
+    session_id: Option<String>,
+    running: bool,
+    helper_mode: Option<String>,
+    #[ts(optional = nullable)]
+    phase: Option<String>,
+    source: Option<String>,
+    updated_at_ms: Option<f64>,
+    #[ts(optional = nullable)]
+    persisted_thread_id: Option<String>,
+    #[ts(optional = nullable)]
+    runtime_thread_id: Option<String>,
+    #[ts(optional = nullable)]
+    local_thread_id: Option<String>,
+    #[ts(optional = nullable)]
+    thread_handle: Option<String>,
+    #[ts(optional = nullable)]
+    turn_id: Option<String>,
+    #[ts(optional = nullable)]
+    last_error_code: Option<String>,
+    #[ts(optional = nullable)]
+    last_error: Option<String>,
+    disabled_tools: Option<Vec<String>>,
+    #[ts(optional = nullable)]
+    pending_server_request_count: Option<f64>,
+    #[ts(optional = nullable)]
+    ingest_enqueued_event_count: Option<f64>,
+    #[ts(optional = nullable)]
+    ingest_skipped_event_count: Option<f64>,
+    #[ts(optional = nullable)]
+    ingest_enqueued_by_kind: Option<Vec<bridge_process::IngestMetricEntry>>,
+    #[ts(optional = nullable)]
+    ingest_skipped_by_kind: Option<Vec<bridge_process::IngestMetricEntry>>,
+}
+
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(optional_fields)]
+struct HelperEvent {
+    session_id: Option<String>,
+    kind: String,
+    thread_id: String,
+    turn_id: Option<String>,
+    stream_id: Option<String>,
+}
+
+// Host lifecycle messages and raw app-server messages; only host messages have a `kind`.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(optional_fields)]
+struct GlobalMessage {
+    session_id: Option<String>,
+    kind: Option<String>,
+    #[serde(flatten)]
+    detail: MessageFields,
+}
+
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(optional_fields)]
+struct ProtocolError {
+    session_id: Option<String>,
+    message: String,
+    line: Option<String>,
+    command: Option<String>,
+    stage: Option<StartStage>,
+}
+
+// The rest of a global message, whatever the host or app-server put there.
+#[derive(Serialize)]
+struct MessageFields(Map<String, Value>);
+
+impl TS for MessageFields {
+    type WithoutGenerics = Self;
+    type OptionInnerType = Self;
+
+    fn name() -> String {
+        "Record<string, unknown>".to_string()
+    }
+
+    fn inline() -> String {
+        Self::name()
+    }
+
+    fn inline_flattened() -> String {
+        Self::name()
+    }
+
+    fn decl() -> String {
+        panic!("{} cannot be declared", Self::name())
+    }
+
+    fn decl_concrete() -> String {
+        panic!("{} cannot be declared", Self::name())
+    }
+}
+
+struct Declaration {
+    name: String,
+    source: String,
+    references: BTreeSet<String>,
+}
+
+struct TypeRef {
+    rust: &'static str,
+    ts: String,
+    references: BTreeSet<String>,
+}
+
+struct Signature {
+    name: String,
+    args: Vec<(String, String)>,
+    result: String,
+}
+
+struct Command {
+    name: &'static str,
+    args: Vec<(&'static str, TypeRef)>,
+    result: TypeRef,
+}
+
+// Names of the declared types a type mentions, itself included.
+fn references<T: TS + 'static + ?Sized>() -> BTreeSet<String> {
+    struct Collect(BTreeSet<String>);
+    impl TypeVisitor for Collect {
+        fn visit<T: TS + 'static + ?Sized>(&mut self) {
+            if let Some(dependency) = Dependency::from_ty::<T>() {
+                self.0.insert(dependency.ts_name);
+            }
+        }
+    }
+    let mut collect = Collect(BTreeSet::new());
+    collect.visit::<T>();
+    T::visit_dependencies(&mut collect);
+    T::visit_generics(&mut collect);
+    collect.0
+}
+
+macro_rules! declarations {
+    ($($ty:ty),* $(,)?) => {
+        vec![$(Declaration {
+            name: <$ty as TS>::ident(),
+            source: <$ty as TS>::decl(),
+            references: references::<$ty>(),
+        }),*]
+    };
+}
+
+macro_rules! type_ref {
+    ($ty:ty) => {
+        TypeRef { rust: stringify!($ty), ts: <$ty as TS>::name(), references: references::<$ty>() }
+    };
+}
+
+// Argument types are written as in the command signatures, minus injected arguments.
+macro_rules! commands {
+    ($($name:ident($($arg:ident: $ty:ty),*) -> $result:ty;)*) => {
+        vec![$(Command {
+            name: stringify!($name),
+            args: vec![$((stringify!($arg), type_ref!($ty))),*],
+            result: type_ref!($result),
+        }),*]
+    };
+}
+
+fn declarations() -> Vec<Declaration> {
+    declarations![
+        StartBridgeConfig,
+        bridge_process::ActorContext,
+        helper_process_policy::HelperProcessConfig,
+        OpenThreadConfig,
+        ServerRequestResponse<()>,
+        RequestId,
+        CommandExecutionRequestApprovalResponse,
+        CommandExecutionApprovalDecision,
+        FileChangeRequestApprovalResponse,
+        FileChangeApprovalDecision,
+        ToolRequestUserInputResponse,
+        ToolRequestUserInputAnswer,
+        GetAccountParams,
+        LoginAccountConfig,
+        LoginAccountParams,
+        CancelLoginAccountParams,
+        ChatgptAuthTokensRefreshResponse,
+        SetDisabledToolsConfig,
+        helper_shutdown::StopBridgeConfig,
+        helper_shutdown::StopReport,
+        helper_shutdown::StopPhase,
+        bridge_process::BridgeStateSnapshot,
+        bridge_process::IngestMetricEntry,
+        audit_verification::AuditVerification,
+        audit_verification::AuditVerificationFailure,
+        session_registry::SessionSummary,
+        event_routing::EventSubscriptionConfig,
+        helper_resolution::ResolutionTrace,
+        helper_resolution::CandidateOutcome,
+        helper_resolution::CandidateStatus,
+        bridge_doctor::DoctorConfig,
+        bridge_doctor::DoctorReport,
+        bridge_doctor::CheckStatus,
+        bridge_doctor::DoctorCheck,
+        diagnostics_bundle::DiagnosticsExport,
+        BridgeStateEvent,
+        HelperEvent,
+        GlobalMessage,
+        ProtocolError,
+        StartStage,
+        instance_lock::SecondInstance,
+    ]
+}
+
+// In BRIDGE_COMMANDS + HOST_COMMANDS order.
+fn commands() -> Vec<Command> {
+    commands! {
+        start_bridge(session_id: Option<String>, config: StartBridgeConfig) -> ();
+        open_thread(session_id: Option<String>, config: OpenThreadConfig) -> ();
+        refresh_local_threads(session_id: Option<String>) -> ();
+        send_user_turn(session_id: Option<String>, text: String) -> ();
+        interrupt_turn(session_id: Option<String>) -> ();
+        respond_command_approval(session_id: Option<String>, config: Validated<RespondCommandApprovalConfig>) -> ();
+        respond_file_change_approval(session_id: Option<String>, config: Validated<RespondFileChangeApprovalConfig>) -> ();
+        respond_tool_user_input(session_id: Option<String>, config: Validated<RespondToolUserInputConfig>) -> ();
+        read_account(session_id: Option<String>, config: Validated<GetAccountParams>) -> ();
+        login_account(session_id: Option<String>, config: Validated<LoginAccountConfig>) -> ();
+        cancel_account_login(session_id: Option<String>, config: Validated<CancelLoginAccountParams>) -> ();
+        logout_account(session_id: Option<String>) -> ();
+        read_account_rate_limits(session_id: Option<String>) -> ();
+        respond_chatgpt_auth_tokens_refresh(
+            session_id: Option<String>,
+            config: Validated<RespondChatgptAuthTokensRefreshConfig>
+        ) -> ();
+        set_disabled_tools(session_id: Option<String>, config: Validated<SetDisabledToolsConfig>) -> ();
+        stop_bridge(session_id: Option<String>, config: Option<helper_shutdown::StopBridgeConfig>) -> helper_shutdown::StopReport;
+        get_bridge_state(session_id: Option<String>) -> bridge_process::BridgeStateSnapshot;
+        verify_audit_log() -> audit_verification::AuditVerification;
+        list_sessions() -> Vec<session_registry::SessionSummary>;
+        stop_session(session_id: String, config: Option<helper_shutdown::StopBridgeConfig>) -> helper_shutdown::StopReport;
+        subscribe_events(config: Option<event_routing::EventSubscriptionConfig>) -> ();
+        unsubscribe_events() -> ();
+        describe_helper_resolution() -> helper_resolution::ResolutionTrace;
+        bridge_doctor(config: Option<bridge_doctor::DoctorConfig>) -> bridge_doctor::DoctorReport;
+        export_diagnostics() -> diagnostics_bundle::DiagnosticsExport;
+    }
+}
+
+// Tauri takes command arguments by their camelCase names.
+fn camel_case(name: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for ch in name.chars() {
+        if ch == '_' {
+            upper = true;
+        } else if upper {
+            out.push(ch.to_ascii_uppercase());
+            upper = false;
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+fn command_args(args: &[(&str, TypeRef)]) -> String {
+    if args.is_empty() {
+        return "Record<string, never>".to_string();
+    }
+    let fields = args
+        .iter()
+        .map(|(name, ty)| {
+            let optional = if ty.rust.starts_with("Option") { "?" } else { "" };
+            format!("{}{optional}: {}", camel_case(name), ty.ts)
+        })
+        .collect::<Vec<_>>();
+    format!("{{ {} }}", fields.join("; "))
+}
+
+fn quoted_list(names: impl IntoIterator<Item = impl std::fmt::Display>) -> String {
+    names.into_iter().map(|name| format!("  \"{name}\",\n")).collect()
+}
+
+fn bindings() -> String {
+    let mut out = String::from(
+        "// AUTO-GENERATED FILE. DO NOT EDIT.\n// Source: src-tauri/src/tauri_bindings.rs; regenerate with `pnpm run tauri:bindings`.\n",
+    );
+    for declaration in declarations() {
+        out.push_str(&format!("\nexport {}\n", declaration.source));
+    }
+
+    let commands = commands();
+    out.push_str("\nexport type TauriCommands = {\n");
+    for command in &commands {
+        out.push_str(&format!(
+            "  {}: {{\n    args: {};\n    result: {};\n  }};\n",
+            command.name,
+            command_args(&command.args),
+            command.result.ts
+        ));
+    }
+    out.push_str("};\n\nexport type TauriEvents = {\n");
+    for (event, payload) in HOST_EVENTS {
+        out.push_str(&format!("  \"{event}\": {payload};\n"));
+    }
+    out.push_str("};\n\n");
+    out.push_str(concat!(
+        "export type TauriCommandName = keyof TauriCommands;\n",
+        "export type TauriCommandArgs<K extends TauriCommandName> = TauriCommands[K][\"args\"];\n",
+        "export type TauriCommandResult<K extends TauriCommandName> = TauriCommands[K][\"result\"];\n",
+        "export type TauriEventName = keyof TauriEvents;\n",
+        "export type TauriEventPayload<K extends TauriEventName> = TauriEvents[K];\n",
+    ));
+    out.push_str(&format!(
+        "\nexport const TAURI_COMMAND_NAMES: ReadonlyArray<TauriCommandName> = [\n{}];\n",
+        quoted_list(commands.iter().map(|command| command.name))
+    ));
+    out.push_str(&format!(
+        "\nexport const TAURI_EVENT_NAMES: ReadonlyArray<TauriEventName> = [\n{}];\n",
+        quoted_list(HOST_EVENTS.iter().map(|(event, _)| event))
+    ));
+    out
+}
+
+// Each #[tauri::command] in `source`, with injected arguments left out and
+// `Result<T, String>` unwrapped to `T`.
+fn command_signatures(source: &str) -> Vec<Signature> {
+    let mut signatures = Vec::new();
+    let mut rest = source;
+    while let Some(at) = rest.find("#[tauri::command]") {
+        rest = &rest[at + "#[tauri::command]".len()..];
+        let name_start = rest.find("fn ").expect("#[tauri::command] is not followed by a fn") + "fn ".len();
+        let open = name_start + rest[name_start..].find('(').expect("command fn has no parameter list");
+        let name = rest[name_start..open].trim().to_string();
+
+        let mut depth = 0;
+        let mut close = open;
+        for (offset, ch) in rest[open..].char_indices() {
+            match ch {
+                '(' | '<' => depth += 1,
+                ')' | '>' if depth > 0 => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                close = open + offset;
+                break;
+            }
+        }
+        let args = split_top_level(&rest[open + 1..close])
+            .into_iter()
+            .filter_map(|param| {
+                let (arg, ty) = param.split_once(':')?;
+                let ty = normalize(ty);
+                (!INJECTED_ARGUMENTS.iter().any(|injected| ty.starts_with(injected))).then(|| (arg.trim().to_string(), ty))
+            })
+            .collect();
+
+        let body = close + rest[close..].find('{').expect("command fn has no body");
+        let output = normalize(rest[close + 1..body].trim().trim_start_matches("->"));
+        let result = match output.strip_prefix("Result<").and_then(|inner| inner.strip_suffix(",String>")) {
+            Some(ok) => ok.to_string(),
+            None if output.is_empty() => "()".to_string(),
+            None => output,
+        };
+        signatures.push(Signature { name, args, result });
+    }
+    signatures
+}
+
+fn split_top_level(params: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, ch) in params.char_indices() {
+        match ch {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(params[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(params[start..].trim());
+    parts.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+fn normalize(ty: &str) -> String {
+    ty.chars().filter(|ch| !ch.is_whitespace()).collect()
+}
+
+#[test]
+fn commands_follow_the_manifest() {
+    let listed = commands().iter().map(|command| command.name).collect::<Vec<_>>();
+    let manifest = BRIDGE_COMMANDS.iter().chain(HOST_COMMANDS).copied().collect::<Vec<_>>();
+    assert_eq!(listed, manifest, "COMMANDS must list BRIDGE_COMMANDS + HOST_COMMANDS, in that order");
+}
+
+#[test]
+fn commands_match_their_signatures() {
+    let signatures = command_signatures(MAIN_SOURCE)
+        .into_iter()
+        .chain(command_signatures(FORWARD_COMMANDS_SOURCE))
+        .collect::<Vec<_>>();
+    for command in commands() {
+        let signature = signatures
+            .iter()
+            .find(|signature| signature.name == command.name)
+            .unwrap_or_else(|| panic!("{} has no #[tauri::command] fn", command.name));
+        let listed = command
+            .args
+            .iter()
+            .map(|(name, ty)| (name.to_string(), normalize(ty.rust)))
+            .collect::<Vec<_>>();
+        assert_eq!(listed, signature.args, "COMMANDS lists other arguments for {} than its signature", command.name);
+        assert_eq!(
+            normalize(command.result.rust),
+            signature.result,
+            "COMMANDS lists another result for {} than its signature",
+            command.name
+        );
+    }
+}
+
+#[test]
+fn every_referenced_type_is_declared() {
+    let declarations = declarations();
+    let mut declared = BTreeSet::new();
+    for declaration in &declarations {
+        assert!(declared.insert(declaration.name.clone()), "{} is declared twice", declaration.name);
+    }
+    let commands = commands();
+    let referenced = declarations
+        .iter()
+        .map(|declaration| (declaration.name.as_str(), &declaration.references))
+        .chain(commands.iter().flat_map(|command| {
+            command
+                .args
+                .iter()
+                .map(|(_, ty)| ty)
+                .chain([&command.result])
+                .map(|ty| (command.name, &ty.references))
+        }));
+    for (owner, references) in referenced {
+        let missing = references.difference(&declared).collect::<Vec<_>>();
+        assert!(missing.is_empty(), "{owner} reaches types missing from declarations(): {missing:?}");
+    }
+    for (event, payload) in HOST_EVENTS {
+        assert!(declared.contains(*payload), "{event} names payload type {payload}, which is not declared");
+    }
+}
+
+// ts-rs writes 64-bit integers as `bigint`; those fields need `#[ts(as = "f64")]`.
+#[test]
+fn declarations_use_json_numbers() {
+    for declaration in declarations() {
+        assert!(!declaration.source.contains("bigint"), "{} has a bigint field: {}", declaration.name, declaration.source);
+    }
+}
+
+#[test]
+fn bindings_are_up_to_date() {
+    let expected = bindings();
+    if std::env::var(BINDINGS_WRITE_ENV).is_ok_and(|value| value == "1") {
+        std::fs::write(BINDINGS_PATH, &expected).unwrap_or_else(|e| panic!("failed to write {BINDINGS_PATH}: {e}"));
+        return;
+    }
+    let current = std::fs::read_to_string(BINDINGS_PATH).unwrap_or_default();
+    assert!(
+        current == expected,
+        "[E_TAURI_BINDINGS_DRIFT] {BINDINGS_PATH} is out of date with the host commands; run `pnpm run tauri:bindings` and commit the result."
+    );
+}
@@ -0,0 +1,39 @@
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Manager};
+
+use crate::main_window;
+
+const TRAY_ID: &str = "codex-host";
+const MENU_OPEN: &str = "open";
+const MENU_QUIT: &str = "quit";
+
+// Background mode keeps the process alive without a window; the tray is the way
+// back to the window and the explicit quit that stops the helper.
+pub fn install(app: &AppHandle) -> Result<(), String> {
+    let open = MenuItem::with_id(app, MENU_OPEN, "Open", true, None::<&str>)
+        .map_err(|e| format!("failed to create tray menu: {e}"))?;
+    let quit = MenuItem::with_id(app, MENU_QUIT, "Quit", true, None::<&str>)
+        .map_err(|e| format!("failed to create tray menu: {e}"))?;
+    let menu = Menu::with_items(app, &[&open, &quit]).map_err(|e| format!("failed to create tray menu: {e}"))?;
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(app.package_info().name.clone())
+        .menu(&menu)
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            MENU_OPEN => {
+                if let Err(message) = main_window::show(app) {
+                    eprintln!("{message}");
+                }
+            }
+            // Goes through ExitRequested, so the helper is stopped before the process exits.
+            MENU_QUIT => app.exit(0),
+            _ => {}
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder
+        .build(app)
+        .map(|_| ())
+        .map_err(|e| format!("failed to create tray icon: {e}"))
+}
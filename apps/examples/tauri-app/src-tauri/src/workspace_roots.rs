@@ -0,0 +1,179 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::host_config::HostConfig;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkspaceScopeError {
+    RootsNotConfigured,
+    RootInvalid { root: String, reason: String },
+    CwdUnresolvable { cwd: String, reason: String },
+    CwdOutsideWorkspace { cwd: String, resolved: String, roots: Vec<String> },
+}
+
+impl WorkspaceScopeError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            WorkspaceScopeError::RootsNotConfigured => "E_TAURI_WORKSPACE_ROOTS_UNSET",
+            WorkspaceScopeError::RootInvalid { .. } => "E_TAURI_WORKSPACE_ROOT_INVALID",
+            WorkspaceScopeError::CwdUnresolvable { .. } => "E_TAURI_CWD_UNRESOLVABLE",
+            WorkspaceScopeError::CwdOutsideWorkspace { .. } => "E_TAURI_CWD_OUTSIDE_WORKSPACE",
+        }
+    }
+}
+
+impl fmt::Display for WorkspaceScopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] ", self.code())?;
+        match self {
+            WorkspaceScopeError::RootsNotConfigured => {
+                write!(f, "no workspace roots are configured under plugins.codexHost.workspaceRoots")
+            }
+            WorkspaceScopeError::RootInvalid { root, reason } => {
+                write!(f, "workspace root {root} is not usable: {reason}")
+            }
+            WorkspaceScopeError::CwdUnresolvable { cwd, reason } => {
+                write!(f, "cwd {cwd} cannot be resolved: {reason}")
+            }
+            WorkspaceScopeError::CwdOutsideWorkspace { cwd, resolved, roots } => write!(
+                f,
+                "cwd {cwd} (resolved to {resolved}) is outside the configured workspace roots: {}",
+                roots.join(", ")
+            ),
+        }
+    }
+}
+
+impl From<WorkspaceScopeError> for String {
+    fn from(error: WorkspaceScopeError) -> Self {
+        error.to_string()
+    }
+}
+
+pub struct WorkspaceRoots {
+    roots: Vec<PathBuf>,
+}
+
+impl WorkspaceRoots {
+    // Roots come from the app config only. Without any, every cwd is rejected
+    // rather than falling back to wherever the host happened to be launched.
+    pub fn from_app_config(app: &AppHandle) -> Result<Self, WorkspaceScopeError> {
+        let home = app.path().home_dir().ok();
+        let configured = app
+            .state::<HostConfig>()
+            .workspace_roots
+            .iter()
+            .map(|root| expand_home(root, home.as_deref()))
+            .collect();
+        Self::new(configured)
+    }
+
+    fn new(configured: Vec<PathBuf>) -> Result<Self, WorkspaceScopeError> {
+        if configured.is_empty() {
+            return Err(WorkspaceScopeError::RootsNotConfigured);
+        }
+        let roots = configured
+            .iter()
+            .map(|root| canonical_dir(root).map_err(|reason| WorkspaceScopeError::RootInvalid {
+                root: root.display().to_string(),
+                reason,
+            }))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { roots })
+    }
+
+    // Returns the canonical cwd to forward to the helper. An omitted cwd resolves
+    // to the first root, so the helper never runs in an unchecked directory.
+    pub fn resolve_cwd(&self, requested: Option<&str>) -> Result<String, WorkspaceScopeError> {
+        let requested = match requested.map(str::trim) {
+            Some(cwd) if !cwd.is_empty() => cwd,
+            _ => return Ok(self.roots[0].to_string_lossy().to_string()),
+        };
+
+        let resolved = canonical_dir(Path::new(requested)).map_err(|reason| WorkspaceScopeError::CwdUnresolvable {
+            cwd: requested.to_string(),
+            reason,
+        })?;
+        if self.roots.iter().any(|root| resolved.starts_with(root)) {
+            return Ok(resolved.to_string_lossy().to_string());
+        }
+        Err(WorkspaceScopeError::CwdOutsideWorkspace {
+            cwd: requested.to_string(),
+            resolved: resolved.display().to_string(),
+            roots: self.roots.iter().map(|root| root.display().to_string()).collect(),
+        })
+    }
+}
+
+pub fn resolve_helper_cwd(app: &AppHandle, requested: Option<&str>) -> Result<String, WorkspaceScopeError> {
+    WorkspaceRoots::from_app_config(app)?.resolve_cwd(requested)
+}
+
+// `~` and `~/…` expand to the user's home dir so a shipped config does not have
+// to hard-code one machine's paths.
+fn expand_home(root: &str, home: Option<&Path>) -> PathBuf {
+    match (root.strip_prefix('~'), home) {
+        (Some(""), Some(home)) => home.to_path_buf(),
+        (Some(rest), Some(home)) if rest.starts_with(['/', '\\']) => home.join(&rest[1..]),
+        _ => PathBuf::from(root),
+    }
+}
+
+// Canonicalization resolves symlinks and `..`, so a link inside a root that
+// points elsewhere is judged by its target.
+fn canonical_dir(path: &Path) -> Result<PathBuf, String> {
+    if !path.is_absolute() {
+        return Err("path must be absolute".to_string());
+    }
+    let resolved = std::fs::canonicalize(path).map_err(|e| e.to_string())?;
+    if !resolved.is_dir() {
+        return Err("path is not a directory".to_string());
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codex-workspace-roots-{}-{name}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::canonicalize(dir).unwrap()
+    }
+
+    #[test]
+    fn no_configured_roots_rejects_every_cwd() {
+        let error = WorkspaceRoots::new(Vec::new()).err().unwrap();
+        assert_eq!(error, WorkspaceScopeError::RootsNotConfigured);
+        assert!(error.to_string().starts_with("[E_TAURI_WORKSPACE_ROOTS_UNSET]"));
+    }
+
+    #[test]
+    fn omitted_cwd_resolves_to_the_first_root() {
+        let root = scratch_dir("first");
+        let roots = WorkspaceRoots::new(vec![root.clone()]).unwrap();
+        assert_eq!(roots.resolve_cwd(None).unwrap(), root.to_string_lossy());
+        assert_eq!(roots.resolve_cwd(Some("  ")).unwrap(), root.to_string_lossy());
+    }
+
+    #[test]
+    fn cwd_outside_the_roots_is_rejected_after_canonicalization() {
+        let root = scratch_dir("inside");
+        let outside = scratch_dir("outside");
+        let roots = WorkspaceRoots::new(vec![root.clone()]).unwrap();
+        let escaped = root.join("..").join(outside.file_name().unwrap());
+        let error = roots.resolve_cwd(Some(&escaped.to_string_lossy())).unwrap_err();
+        assert_eq!(error.code(), "E_TAURI_CWD_OUTSIDE_WORKSPACE");
+    }
+
+    #[test]
+    fn home_prefix_expands() {
+        let home = Path::new("/home/dev");
+        assert_eq!(expand_home("~", Some(home)), PathBuf::from("/home/dev"));
+        assert_eq!(expand_home("~/src", Some(home)), PathBuf::from("/home/dev/src"));
+        assert_eq!(expand_home("~other/src", Some(home)), PathBuf::from("~other/src"));
+        assert_eq!(expand_home("/srv/repos", Some(home)), PathBuf::from("/srv/repos"));
+    }
+}
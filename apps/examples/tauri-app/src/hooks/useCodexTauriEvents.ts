@@ -1,6 +1,7 @@
 import { useEffect, useRef, type Dispatch, type SetStateAction } from "react";
-import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import type { BridgeState } from "../lib/tauriBridge";
+import type { TauriEventPayload } from "../lib/tauriBindings.generated";
 import type { ToastItem } from "../components/Toast";
 
 export type PendingAuthRefreshRequest = {
@@ -87,7 +88,7 @@ export function useCodexTauriEvents({
             return next;
           });
         }),
-        listen<{ kind: string; turnId?: string; threadId?: string }>("codex:event", (event) => {
+        getCurrentWebviewWindow().listen<TauriEventPayload<"codex:event">>("codex:event", (event) => {
           const line = `${event.payload.kind} (${event.payload.turnId ?? "-"})`;
           const id = `${Date.now()}-${Math.random().toString(36).slice(2, 8)}`;
           setRuntimeLogRef.current((prev) => [{ id, line }, ...prev].slice(0, 8));
@@ -96,12 +97,12 @@ export function useCodexTauriEvents({
             setBridgeRef.current((prev) => ({ ...prev, threadHandle: threadId }));
           }
         }),
-        listen<{ message: string }>("codex:protocol_error", (event) => {
+        getCurrentWebviewWindow().listen<TauriEventPayload<"codex:protocol_error">>("codex:protocol_error", (event) => {
           setBridgeRef.current((prev) => ({ ...prev, lastError: event.payload.message }));
           console.error("[codex:protocol_error]", event.payload.message, event.payload);
           addToastRef.current("error", event.payload.message);
         }),
-        listen<Record<string, unknown>>("codex:global_message", (event) => {
+        getCurrentWebviewWindow().listen<TauriEventPayload<"codex:global_message">>("codex:global_message", (event) => {
           const payload = event.payload ?? {};
           const record = asRecord(payload);
           if (!record) {
@@ -0,0 +1,237 @@
+// AUTO-GENERATED FILE. DO NOT EDIT.
+// Source: src-tauri/src/tauri_bindings.rs; regenerate with `pnpm run tauri:bindings`.
+
+export type StartBridgeConfig = { convexUrl: string, actor: ActorContext, sessionId: string, startSource?: string | null, model?: string | null, cwd?: string | null, disabledTools?: Array<string> | null, deltaThrottleMs?: number | null, saveStreamDeltas?: boolean | null, helperProcess?: HelperProcessConfig | null, readyTimeoutMs?: number | null, };
+
+export type ActorContext = { userId?: string | null, };
+
+export type HelperProcessConfig = { env?: { [key in string]?: string } | null, maxAddressSpaceMb?: number | null, maxOpenFiles?: number | null, nice?: number | null, };
+
+export type OpenThreadConfig = { strategy: string, threadId?: string | null, model?: string | null, cwd?: string | null, dynamicTools?: unknown, };
+
+export type ServerRequestResponse<T> = { requestId: RequestId, } & T;
+
+export type RequestId = string | number;
+
+export type CommandExecutionRequestApprovalResponse = { decision: CommandExecutionApprovalDecision, };
+
+export type CommandExecutionApprovalDecision = "accept" | "acceptForSession" | { "acceptWithExecpolicyAmendment": { execpolicy_amendment: Array<string>, } } | "decline" | "cancel";
+
+export type FileChangeRequestApprovalResponse = { decision: FileChangeApprovalDecision, };
+
+export type FileChangeApprovalDecision = "accept" | "acceptForSession" | "decline" | "cancel";
+
+export type ToolRequestUserInputResponse = { answers: { [key in string]?: ToolRequestUserInputAnswer }, };
+
+export type ToolRequestUserInputAnswer = { answers: Array<string>, };
+
+export type GetAccountParams = { refreshToken?: boolean | null, };
+
+export type LoginAccountConfig = { params: LoginAccountParams, };
+
+export type LoginAccountParams = { "type": "apiKey", apiKey: string, } | { "type": "chatgpt" } | { "type": "chatgptAuthTokens", accessToken: string, chatgptAccountId: string, chatgptPlanType?: string | null, };
+
+export type CancelLoginAccountParams = { loginId: string, };
+
+export type ChatgptAuthTokensRefreshResponse = { accessToken: string, chatgptAccountId: string, chatgptPlanType?: string | null, };
+
+export type SetDisabledToolsConfig = { tools: Array<string>, };
+
+export type StopBridgeConfig = { interruptFirst?: boolean | null, interruptTimeoutMs?: number | null, ackTimeoutMs?: number | null, exitTimeoutMs?: number | null, };
+
+export type StopReport = { wasRunning: boolean, interrupted: boolean, acknowledged: boolean, exited: boolean, timedOutPhase: StopPhase | null, elapsedMs: number, };
+
+export type StopPhase = "interrupt" | "ack" | "exit";
+
+export type BridgeStateSnapshot = { running: boolean, phase: string | null, persistedThreadId: string | null, runtimeThreadId: string | null, localThreadId: string | null, threadHandle: string | null, turnId: string | null, lastErrorCode: string | null, lastError: string | null, disabledTools: Array<string>, pendingServerRequestCount: number | null, ingestEnqueuedEventCount: number | null, ingestSkippedEventCount: number | null, ingestEnqueuedByKind: Array<IngestMetricEntry> | null, ingestSkippedByKind: Array<IngestMetricEntry> | null, };
+
+export type IngestMetricEntry = { kind: string, count: number, };
+
+export type AuditVerification = { ok: boolean, directory: string, files: Array<string>, entries: number, headHash: string, failure: AuditVerificationFailure | null, };
+
+export type AuditVerificationFailure = { file: string, line: number, reason: string, };
+
+export type SessionSummary = { sessionId: string, running: boolean, phase: string | null, cwd: string | null, localThreadId: string | null, turnId: string | null, lastErrorCode: string | null, };
+
+export type EventSubscriptionConfig = { sessionIds?: Array<string> | null, threadIds?: Array<string> | null, };
+
+export type ResolutionTrace = { candidates: Array<CandidateOutcome>, mode: string | null, command: string | null, args: Array<string>, };
+
+export type CandidateOutcome = { candidate: string, path: string | null, status: CandidateStatus, reason: string | null, };
+
+export type CandidateStatus = "selected" | "rejected" | "skipped";
+
+export type DoctorConfig = { convexUrl?: string | null, cwd?: string | null, localEndpoint?: string | null, handshakeTimeoutMs?: number | null, skipHandshake?: boolean | null, };
+
+export type DoctorReport = { status: CheckStatus, checks: Array<DoctorCheck>, generatedAtMs: number, };
+
+export type CheckStatus = "pass" | "warn" | "fail" | "skip";
+
+export type DoctorCheck = { id: string, status: CheckStatus, summary: string, detail: unknown, };
+
+export type DiagnosticsExport = { path: string, sizeBytes: number, entries: Array<string>, };
+
+export type BridgeStateEvent = { sessionId?: string, running: boolean, helperMode?: string, phase?: string | null, source?: string, updatedAtMs?: number, persistedThreadId?: string | null, runtimeThreadId?: string | null, localThreadId?: string | null, threadHandle?: string | null, turnId?: string | null, lastErrorCode?: string | null, lastError?: string | null, disabledTools?: Array<string>, pendingServerRequestCount?: number | null, ingestEnqueuedEventCount?: number | null, ingestSkippedEventCount?: number | null, ingestEnqueuedByKind?: Array<IngestMetricEntry> | null, ingestSkippedByKind?: Array<IngestMetricEntry> | null, };
+
+export type HelperEvent = { sessionId?: string, kind: string, threadId: string, turnId?: string, streamId?: string, };
+
+export type GlobalMessage = { sessionId?: string, kind?: string, } & Record<string, unknown>;
+
+export type ProtocolError = { sessionId?: string, message: string, line?: string, command?: string, stage?: StartStage, };
+
+export type StartStage = "spawn" | "handshake" | "convex_connect" | "app_server_init";
+
+export type SecondInstance = { args: Array<string>, cwd: string | null, };
+
+export type TauriCommands = {
+  start_bridge: {
+    args: { sessionId?: string | null; config: StartBridgeConfig };
+    result: null;
+  };
+  open_thread: {
+    args: { sessionId?: string | null; config: OpenThreadConfig };
+    result: null;
+  };
+  refresh_local_threads: {
+    args: { sessionId?: string | null };
+    result: null;
+  };
+  send_user_turn: {
+    args: { sessionId?: string | null; text: string };
+    result: null;
+  };
+  interrupt_turn: {
+    args: { sessionId?: string | null };
+    result: null;
+  };
+  respond_command_approval: {
+    args: { sessionId?: string | null; config: ServerRequestResponse<CommandExecutionRequestApprovalResponse> };
+    result: null;
+  };
+  respond_file_change_approval: {
+    args: { sessionId?: string | null; config: ServerRequestResponse<FileChangeRequestApprovalResponse> };
+    result: null;
+  };
+  respond_tool_user_input: {
+    args: { sessionId?: string | null; config: ServerRequestResponse<ToolRequestUserInputResponse> };
+    result: null;
+  };
+  read_account: {
+    args: { sessionId?: string | null; config: GetAccountParams };
+    result: null;
+  };
+  login_account: {
+    args: { sessionId?: string | null; config: LoginAccountConfig };
+    result: null;
+  };
+  cancel_account_login: {
+    args: { sessionId?: string | null; config: CancelLoginAccountParams };
+    result: null;
+  };
+  logout_account: {
+    args: { sessionId?: string | null };
+    result: null;
+  };
+  read_account_rate_limits: {
+    args: { sessionId?: string | null };
+    result: null;
+  };
+  respond_chatgpt_auth_tokens_refresh: {
+    args: { sessionId?: string | null; config: ServerRequestResponse<ChatgptAuthTokensRefreshResponse> };
+    result: null;
+  };
+  set_disabled_tools: {
+    args: { sessionId?: string | null; config: SetDisabledToolsConfig };
+    result: null;
+  };
+  stop_bridge: {
+    args: { sessionId?: string | null; config?: StopBridgeConfig | null };
+    result: StopReport;
+  };
+  get_bridge_state: {
+    args: { sessionId?: string | null };
+    result: BridgeStateSnapshot;
+  };
+  verify_audit_log: {
+    args: Record<string, never>;
+    result: AuditVerification;
+  };
+  list_sessions: {
+    args: Record<string, never>;
+    result: Array<SessionSummary>;
+  };
+  stop_session: {
+    args: { sessionId: string; config?: StopBridgeConfig | null };
+    result: StopReport;
+  };
+  subscribe_events: {
+    args: { config?: EventSubscriptionConfig | null };
+    result: null;
+  };
+  unsubscribe_events: {
+    args: Record<string, never>;
+    result: null;
+  };
+  describe_helper_resolution: {
+    args: Record<string, never>;
+    result: ResolutionTrace;
+  };
+  bridge_doctor: {
+    args: { config?: DoctorConfig | null };
+    result: DoctorReport;
+  };
+  export_diagnostics: {
+    args: Record<string, never>;
+    result: DiagnosticsExport;
+  };
+};
+
+export type TauriEvents = {
+  "codex:bridge_state": BridgeStateEvent;
+  "codex:event": HelperEvent;
+  "codex:global_message": GlobalMessage;
+  "codex:protocol_error": ProtocolError;
+  "codex:second_instance": SecondInstance;
+};
+
+export type TauriCommandName = keyof TauriCommands;
+export type TauriCommandArgs<K extends TauriCommandName> = TauriCommands[K]["args"];
+export type TauriCommandResult<K extends TauriCommandName> = TauriCommands[K]["result"];
+export type TauriEventName = keyof TauriEvents;
+export type TauriEventPayload<K extends TauriEventName> = TauriEvents[K];
+
+export const TAURI_COMMAND_NAMES: ReadonlyArray<TauriCommandName> = [
+  "start_bridge",
+  "open_thread",
+  "refresh_local_threads",
+  "send_user_turn",
+  "interrupt_turn",
+  "respond_command_approval",
+  "respond_file_change_approval",
+  "respond_tool_user_input",
+  "read_account",
+  "login_account",
+  "cancel_account_login",
+  "logout_account",
+  "read_account_rate_limits",
+  "respond_chatgpt_auth_tokens_refresh",
+  "set_disabled_tools",
+  "stop_bridge",
+  "get_bridge_state",
+  "verify_audit_log",
+  "list_sessions",
+  "stop_session",
+  "subscribe_events",
+  "unsubscribe_events",
+  "describe_helper_resolution",
+  "bridge_doctor",
+  "export_diagnostics",
+];
+
+export const TAURI_EVENT_NAMES: ReadonlyArray<TauriEventName> = [
+  "codex:bridge_state",
+  "codex:event",
+  "codex:global_message",
+  "codex:protocol_error",
+  "codex:second_instance",
+];
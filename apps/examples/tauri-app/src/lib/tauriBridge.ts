@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { createTauriBridgeClient } from "@zakstam/codex-local-component/host/tauri";
 import type { BridgeState } from "@zakstam/codex-local-component/host/tauri";
 
@@ -16,8 +16,9 @@ export const bridge = createTauriBridgeClient(
   (command, args) => invoke(command, args),
   {
     lifecycleSafeSend: true,
+    // Session events are routed per window; the global `listen` never sees them.
     subscribeBridgeState: async (listener) => {
-      return listen<BridgeState>("codex:bridge_state", (event) => {
+      return getCurrentWebviewWindow().listen<BridgeState>("codex:bridge_state", (event) => {
         listener(event.payload);
       });
     },
@@ -1,6 +1,8 @@
 import type { FunctionArgs } from "convex/server";
 import { api } from "../convex/_generated/api";
+import type { BridgeState, TauriBridgeClientCommands } from "@zakstam/codex-local-component/host/tauri";
 import type { ActorContext } from "./lib/tauriBridge";
+import type { TauriCommandArgs, TauriCommandName, TauriCommandResult } from "./lib/tauriBindings.generated";
 
 type Assert<T extends true> = T;
 type Extends<A, B> = A extends B ? true : false;
@@ -114,4 +116,42 @@ type _ResolveOpenTargetArgsAreTyped = Assert<
   >
 >;
 
+// The bridge client's invoke calls against the bindings generated from the Rust
+// host; rebuilding src-tauri after a command signature change surfaces drift here.
+type ClientCommandName = keyof TauriBridgeClientCommands;
+type ClientArgs<K extends ClientCommandName> = TauriBridgeClientCommands[K]["args"];
+type ClientResult<K extends ClientCommandName> = TauriBridgeClientCommands[K]["result"];
+type SentArgs<K extends ClientCommandName> =
+  | Exclude<ClientArgs<K>, undefined>
+  | (undefined extends ClientArgs<K> ? {} : never);
+
+type _ClientCommandsExistOnHost = Assert<Extends<ClientCommandName, TauriCommandName>>;
+
+type _ClientArgsMatchHost = Assert<
+  Extends<
+    { [K in ClientCommandName]: SentArgs<K> },
+    { [K in ClientCommandName]: TauriCommandArgs<K & TauriCommandName> }
+  >
+>;
+
+type _StopResultsMatchHost = Assert<
+  Extends<TauriCommandResult<"stop_bridge" | "stop_session">, ClientResult<"stop_bridge" | "stop_session">>
+>;
+
+type _DiagnosticsExportResultMatchesHost = Assert<
+  Extends<TauriCommandResult<"export_diagnostics">, ClientResult<"export_diagnostics">>
+>;
+
+// The client narrows `phase` to the runtime's phase names; the host reports a string.
+type _BridgeStateResultMatchesHost = Assert<
+  Extends<Omit<TauriCommandResult<"get_bridge_state">, "phase">, Omit<BridgeState, "phase">>
+>;
+
+type _SessionListResultMatchesHost = Assert<
+  Extends<
+    Array<Omit<TauriCommandResult<"list_sessions">[number], "phase">>,
+    Array<Omit<ClientResult<"list_sessions">[number], "phase">>
+  >
+>;
+
 export {};
@@ -10,6 +10,7 @@ export type { LoginAccountParams } from "../protocol/schemas/v2/LoginAccountPara
 export type ActorContext = { userId?: string };
 
 export type BridgeState = {
+  sessionId?: string;
   running: boolean;
   phase?: "idle" | "starting" | "running" | "stopping" | "stopped" | "error";
   source?: "runtime" | "bridge_event" | "protocol_error" | "process_exit";
@@ -43,6 +44,31 @@ export type StartBridgeConfig = {
   disabledTools?: string[];
   deltaThrottleMs?: number;
   saveStreamDeltas?: boolean;
+  helperProcess?: HelperProcessConfig;
+  readyTimeoutMs?: number;
+};
+
+export type StopBridgeConfig = {
+  interruptFirst?: boolean;
+  interruptTimeoutMs?: number;
+  ackTimeoutMs?: number;
+  exitTimeoutMs?: number;
+};
+
+export type StopBridgeReport = {
+  wasRunning: boolean;
+  interrupted: boolean;
+  acknowledged: boolean;
+  exited: boolean;
+  timedOutPhase: "interrupt" | "ack" | "exit" | null;
+  elapsedMs: number;
+};
+
+export type HelperProcessConfig = {
+  env?: Record<string, string>;
+  maxAddressSpaceMb?: number;
+  maxOpenFiles?: number;
+  nice?: number;
 };
 
 export type StartPayload = {
@@ -118,12 +144,25 @@ export type HelperCommand =
   | { type: "stop" }
   | { type: "status" };
 
+type TauriForwardArg = {
+  name: string;
+  rustType: string;
+};
+
+type TauriForwardShape = "config" | { config: string } | ReadonlyArray<TauriForwardArg>;
+
 type TauriBridgeCommandDefinition = {
   id: string;
   helperType?: HelperCommandType;
   tauriCommand?: string;
   permission?: boolean;
   ack?: boolean;
+  // Generates the host's Tauri command. `"config"` passes the `config` argument
+  // through as the helper payload; `{ config: "Type" }` does the same with a
+  // typed Rust argument the host brings into scope, wrapped in the host's
+  // `Validated` so it is schema-checked first; a list packs those arguments
+  // into a JSON object. Commands without it are written by hand in the host.
+  forward?: TauriForwardShape;
 };
 
 export const TAURI_BRIDGE_COMMANDS: ReadonlyArray<TauriBridgeCommandDefinition> = [
@@ -135,15 +174,31 @@ export const TAURI_BRIDGE_COMMANDS: ReadonlyArray<TauriBridgeCommandDefinition>
     helperType: "refresh_local_threads",
     permission: true,
     ack: true,
+    forward: [],
+  },
+  {
+    id: "send_user_turn",
+    tauriCommand: "send_user_turn",
+    helperType: "send_turn",
+    permission: true,
+    ack: true,
+    forward: [{ name: "text", rustType: "String" }],
+  },
+  {
+    id: "interrupt_turn",
+    tauriCommand: "interrupt_turn",
+    helperType: "interrupt",
+    permission: true,
+    ack: true,
+    forward: [],
   },
-  { id: "send_user_turn", tauriCommand: "send_user_turn", helperType: "send_turn", permission: true, ack: true },
-  { id: "interrupt_turn", tauriCommand: "interrupt_turn", helperType: "interrupt", permission: true, ack: true },
   {
     id: "respond_command_approval",
     tauriCommand: "respond_command_approval",
     helperType: "respond_command_approval",
     permission: true,
     ack: true,
+    forward: { config: "RespondCommandApprovalConfig" },
   },
   {
     id: "respond_file_change_approval",
@@ -151,6 +206,7 @@ export const TAURI_BRIDGE_COMMANDS: ReadonlyArray<TauriBridgeCommandDefinition>
     helperType: "respond_file_change_approval",
     permission: true,
     ack: true,
+    forward: { config: "RespondFileChangeApprovalConfig" },
   },
   {
     id: "respond_tool_user_input",
@@ -158,14 +214,23 @@ export const TAURI_BRIDGE_COMMANDS: ReadonlyArray<TauriBridgeCommandDefinition>
     helperType: "respond_tool_user_input",
     permission: true,
     ack: true,
+    forward: { config: "RespondToolUserInputConfig" },
+  },
+  {
+    id: "read_account",
+    tauriCommand: "read_account",
+    helperType: "account_read",
+    permission: true,
+    ack: true,
+    forward: { config: "GetAccountParams" },
   },
-  { id: "read_account", tauriCommand: "read_account", helperType: "account_read", permission: true, ack: true },
   {
     id: "login_account",
     tauriCommand: "login_account",
     helperType: "account_login_start",
     permission: true,
     ack: true,
+    forward: { config: "LoginAccountConfig" },
   },
   {
     id: "cancel_account_login",
@@ -173,14 +238,23 @@ export const TAURI_BRIDGE_COMMANDS: ReadonlyArray<TauriBridgeCommandDefinition>
     helperType: "account_login_cancel",
     permission: true,
     ack: true,
+    forward: { config: "CancelLoginAccountParams" },
+  },
+  {
+    id: "logout_account",
+    tauriCommand: "logout_account",
+    helperType: "account_logout",
+    permission: true,
+    ack: true,
+    forward: [],
   },
-  { id: "logout_account", tauriCommand: "logout_account", helperType: "account_logout", permission: true, ack: true },
   {
     id: "read_account_rate_limits",
     tauriCommand: "read_account_rate_limits",
     helperType: "account_rate_limits_read",
     permission: true,
     ack: true,
+    forward: [],
   },
   {
     id: "respond_chatgpt_auth_tokens_refresh",
@@ -188,6 +262,7 @@ export const TAURI_BRIDGE_COMMANDS: ReadonlyArray<TauriBridgeCommandDefinition>
     helperType: "respond_chatgpt_auth_tokens_refresh",
     permission: true,
     ack: true,
+    forward: { config: "RespondChatgptAuthTokensRefreshConfig" },
   },
   {
     id: "set_disabled_tools",
@@ -195,6 +270,7 @@ export const TAURI_BRIDGE_COMMANDS: ReadonlyArray<TauriBridgeCommandDefinition>
     helperType: "set_disabled_tools",
     permission: true,
     ack: true,
+    forward: { config: "SetDisabledToolsConfig" },
   },
   { id: "stop_bridge", tauriCommand: "stop_bridge", helperType: "stop", permission: true, ack: false },
   { id: "get_bridge_state", tauriCommand: "get_bridge_state", permission: true },
@@ -263,8 +339,118 @@ export type TauriBridgeStateSubscribe = (listener: TauriBridgeStateListener) =>
 export type TauriBridgeClientOptions = {
   subscribeBridgeState?: TauriBridgeStateSubscribe;
   lifecycleSafeSend?: boolean;
+  // Targets one host session; required once more than one session is running.
+  sessionId?: string;
+};
+
+export type EventSubscriptionConfig = {
+  sessionIds?: string[];
+  threadIds?: string[];
+};
+
+export type BridgeSessionSummary = {
+  sessionId: string;
+  running: boolean;
+  phase: BridgeState["phase"] | null;
+  cwd: string | null;
+  localThreadId: string | null;
+  turnId: string | null;
+  lastErrorCode: string | null;
+};
+
+export type BridgeDoctorConfig = {
+  convexUrl?: string;
+  cwd?: string;
+  localEndpoint?: string;
+  handshakeTimeoutMs?: number;
+  skipHandshake?: boolean;
+};
+
+export type BridgeDoctorStatus = "pass" | "warn" | "fail" | "skip";
+
+export type BridgeDoctorCheck = {
+  id:
+    | "helper_resolution"
+    | "node_runtime"
+    | "helper_integrity"
+    | "helper_handshake"
+    | "app_data_dir"
+    | "cwd"
+    | "convex_url"
+    | "local_endpoint";
+  status: BridgeDoctorStatus;
+  summary: string;
+  detail: unknown;
+};
+
+export type BridgeDoctorReport = {
+  status: Exclude<BridgeDoctorStatus, "skip">;
+  checks: BridgeDoctorCheck[];
+  generatedAtMs: number;
+};
+
+export type DiagnosticsExport = {
+  path: string;
+  sizeBytes: number;
+  entries: string[];
 };
 
+export type OpenThreadInvokeConfig = OpenThreadConfig & { threadId?: ThreadHandle };
+
+// What the client sends to and expects back from each Tauri command. `args` is
+// undefined for commands invoked without arguments; the session id is added by
+// the client. Hosts can check this against their own command signatures.
+export type TauriBridgeClientCommands = {
+  start_bridge: { args: { config: StartBridgeConfig }; result: unknown };
+  open_thread: { args: { config: OpenThreadInvokeConfig }; result: unknown };
+  refresh_local_threads: { args: undefined; result: unknown };
+  send_user_turn: { args: { text: string }; result: unknown };
+  interrupt_turn: { args: undefined; result: unknown };
+  respond_command_approval: {
+    args: { config: { requestId: string | number; decision: CommandApprovalDecision } };
+    result: unknown;
+  };
+  respond_file_change_approval: {
+    args: { config: { requestId: string | number; decision: CommandApprovalDecision } };
+    result: unknown;
+  };
+  respond_tool_user_input: {
+    args: { config: { requestId: string | number; answers: Record<string, ToolUserInputAnswer> } };
+    result: unknown;
+  };
+  read_account: { args: { config: { refreshToken?: boolean } }; result: unknown };
+  login_account: { args: { config: { params: ProtocolLoginAccountParams } }; result: unknown };
+  cancel_account_login: { args: { config: { loginId: string } }; result: unknown };
+  logout_account: { args: undefined; result: unknown };
+  read_account_rate_limits: { args: undefined; result: unknown };
+  respond_chatgpt_auth_tokens_refresh: {
+    args: {
+      config: {
+        requestId: string | number;
+        accessToken: string;
+        chatgptAccountId: string;
+        chatgptPlanType?: string | null;
+      };
+    };
+    result: unknown;
+  };
+  set_disabled_tools: { args: { config: { tools: string[] } }; result: unknown };
+  stop_bridge: { args: { config: StopBridgeConfig } | undefined; result: StopBridgeReport };
+  get_bridge_state: { args: undefined; result: BridgeState };
+  list_sessions: { args: undefined; result: BridgeSessionSummary[] };
+  stop_session: { args: { sessionId: string; config?: StopBridgeConfig }; result: StopBridgeReport };
+  subscribe_events: { args: { config: EventSubscriptionConfig }; result: unknown };
+  unsubscribe_events: { args: undefined; result: unknown };
+  bridge_doctor: { args: { config: BridgeDoctorConfig }; result: BridgeDoctorReport };
+  export_diagnostics: { args: undefined; result: DiagnosticsExport };
+};
+
+type TauriBridgeClientCommandName = keyof TauriBridgeClientCommands;
+type TauriBridgeClientInvoke = <K extends TauriBridgeClientCommandName>(
+  command: K,
+  args: TauriBridgeClientCommands[K]["args"],
+) => Promise<TauriBridgeClientCommands[K]["result"]>;
+
 export type TauriBridgeClientSendErrorCode =
   | "E_TAURI_SEND_START_CONFIG_MISSING"
   | "E_TAURI_SEND_AUTO_START_FAILED"
@@ -286,7 +472,7 @@ export type TauriBridgeClient = {
     start(config: StartBridgeConfig): Promise<unknown>;
     openThread(config: OpenThreadConfig): Promise<unknown>;
     refreshLocalThreads(): Promise<unknown>;
-    stop(): Promise<unknown>;
+    stop(config?: StopBridgeConfig): Promise<StopBridgeReport>;
     getState(): Promise<BridgeState>;
     subscribe(listener: TauriBridgeStateListener): Promise<() => void>;
   };
@@ -315,6 +501,18 @@ export type TauriBridgeClient = {
   tools: {
     setDisabled(config: { tools: string[] }): Promise<unknown>;
   };
+  sessions: {
+    list(): Promise<BridgeSessionSummary[]>;
+    stop(sessionId: string, config?: StopBridgeConfig): Promise<StopBridgeReport>;
+  };
+  events: {
+    subscribe(config?: EventSubscriptionConfig): Promise<unknown>;
+    unsubscribe(): Promise<unknown>;
+  };
+  diagnostics: {
+    doctor(config?: BridgeDoctorConfig): Promise<BridgeDoctorReport>;
+    export(): Promise<DiagnosticsExport>;
+  };
 };
 
 const LIFECYCLE_SAFE_SEND_READY_TIMEOUT_MS = 8_000;
@@ -330,16 +528,15 @@ function isBridgeReadyForTurnSend(state: BridgeState): boolean {
   );
 }
 
-function toBridgeStartInvokeConfig(config: StartBridgeConfig): Record<string, unknown> {
+function toBridgeStartInvokeConfig(config: StartBridgeConfig): StartBridgeConfig {
   return { ...config };
 }
 
-function toOpenThreadInvokeConfig(config: OpenThreadConfig): Record<string, unknown> {
-  const invokeConfig: Record<string, unknown> = { ...config };
+function toOpenThreadInvokeConfig(config: OpenThreadConfig): OpenThreadInvokeConfig {
   if (typeof config.threadHandle === "string" && config.threadHandle.length > 0) {
-    invokeConfig.threadId = config.threadHandle;
+    return { ...config, threadId: config.threadHandle };
   }
-  return invokeConfig;
+  return { ...config };
 }
 
 function errorMessage(error: unknown): string {
@@ -359,10 +556,25 @@ function isLifecycleTransientSendError(error: unknown): boolean {
   );
 }
 
-export function createTauriBridgeClient(invoke: TauriInvoke, options?: TauriBridgeClientOptions): TauriBridgeClient {
+function withSessionId(invoke: TauriInvoke, sessionId: string | undefined): TauriInvoke {
+  if (!sessionId) {
+    return invoke;
+  }
+  return <T>(command: string, args?: Record<string, unknown>) => invoke<T>(command, { ...args, sessionId });
+}
+
+function typedInvoke(invoke: TauriInvoke): TauriBridgeClientInvoke {
+  return <K extends TauriBridgeClientCommandName>(command: K, args: TauriBridgeClientCommands[K]["args"]) =>
+    invoke<TauriBridgeClientCommands[K]["result"]>(command, args);
+}
+
+export function createTauriBridgeClient(rawInvoke: TauriInvoke, options?: TauriBridgeClientOptions): TauriBridgeClient {
+  const invoke = typedInvoke(withSessionId(rawInvoke, options?.sessionId));
+  // Host commands that name their session explicitly, or take none, skip the client session id.
+  const hostInvoke = typedInvoke(rawInvoke);
   let cachedStartConfig: StartBridgeConfig | null = null;
 
-  const getState = (): Promise<BridgeState> => invoke("get_bridge_state");
+  const getState = (): Promise<BridgeState> => invoke("get_bridge_state", undefined);
   const start = (config: StartBridgeConfig): Promise<unknown> => {
     cachedStartConfig = { ...config };
     return invoke("start_bridge", { config: toBridgeStartInvokeConfig(config) });
@@ -428,10 +640,10 @@ export function createTauriBridgeClient(invoke: TauriInvoke, options?: TauriBrid
         return invoke("open_thread", { config: toOpenThreadInvokeConfig(config) });
       },
       refreshLocalThreads(): Promise<unknown> {
-        return invoke("refresh_local_threads");
+        return invoke("refresh_local_threads", undefined);
       },
-      stop(): Promise<unknown> {
-        return invoke("stop_bridge");
+      stop(config?: StopBridgeConfig): Promise<StopBridgeReport> {
+        return invoke("stop_bridge", config ? { config } : undefined);
       },
       getState(): Promise<BridgeState> {
         return getState();
@@ -451,7 +663,7 @@ export function createTauriBridgeClient(invoke: TauriInvoke, options?: TauriBrid
         return invoke("send_user_turn", { text });
       },
       interrupt(): Promise<unknown> {
-        return invoke("interrupt_turn");
+        return invoke("interrupt_turn", undefined);
       },
     },
     approvals: {
@@ -476,10 +688,10 @@ export function createTauriBridgeClient(invoke: TauriInvoke, options?: TauriBrid
         return invoke("cancel_account_login", { config });
       },
       logout(): Promise<unknown> {
-        return invoke("logout_account");
+        return invoke("logout_account", undefined);
       },
       readRateLimits(): Promise<unknown> {
-        return invoke("read_account_rate_limits");
+        return invoke("read_account_rate_limits", undefined);
       },
       respondChatgptAuthTokensRefresh(config: {
         requestId: string | number;
@@ -495,6 +707,30 @@ export function createTauriBridgeClient(invoke: TauriInvoke, options?: TauriBrid
         return invoke("set_disabled_tools", { config });
       },
     },
+    sessions: {
+      list(): Promise<BridgeSessionSummary[]> {
+        return hostInvoke("list_sessions", undefined);
+      },
+      stop(sessionId: string, config?: StopBridgeConfig): Promise<StopBridgeReport> {
+        return hostInvoke("stop_session", config ? { sessionId, config } : { sessionId });
+      },
+    },
+    events: {
+      subscribe(config?: EventSubscriptionConfig): Promise<unknown> {
+        return hostInvoke("subscribe_events", { config: config ?? {} });
+      },
+      unsubscribe(): Promise<unknown> {
+        return hostInvoke("unsubscribe_events", undefined);
+      },
+    },
+    diagnostics: {
+      doctor(config?: BridgeDoctorConfig): Promise<BridgeDoctorReport> {
+        return hostInvoke("bridge_doctor", { config: config ?? {} });
+      },
+      export(): Promise<DiagnosticsExport> {
+        return hostInvoke("export_diagnostics", undefined);
+      },
+    },
   };
 }
 
@@ -502,6 +738,48 @@ function toKebabCase(value: string): string {
   return value.replaceAll("_", "-");
 }
 
+function toCamelCase(value: string): string {
+  return value.replace(/_([a-z])/g, (_match, letter: string) => letter.toUpperCase());
+}
+
+function renderRustForwardCommand(tauriCommand: string, forward: TauriForwardShape): string[] {
+  const typed = forward !== "config" && "config" in forward;
+  const args: ReadonlyArray<TauriForwardArg> =
+    forward === "config"
+      ? [{ name: "config", rustType: "serde_json::Value" }]
+      : "config" in forward
+        ? [{ name: "config", rustType: `Validated<${forward.config}>` }]
+        : forward;
+  const payload =
+    forward === "config"
+      ? "config"
+      : "config" in forward
+        ? "payload"
+        : forward.length === 0
+          ? "serde_json::json!({})"
+          : `serde_json::json!({ ${forward.map((arg) => `\"${toCamelCase(arg.name)}\": ${arg.name}`).join(", ")} })`;
+  return [
+    "#[tauri::command]",
+    `async fn ${tauriCommand}(`,
+    "    app: tauri::AppHandle,",
+    "    state: tauri::State<'_, BridgeHostState>,",
+    "    session_id: Option<String>,",
+    ...args.map((arg) => `    ${arg.name}: ${arg.rustType},`),
+    ") -> Result<(), String> {",
+    ...(typed
+      ? [
+          "    let payload = serde_json::to_value(config)",
+          `        .map_err(|e| format!(\"failed to encode ${tauriCommand} payload: {e}\"))?;`,
+        ]
+      : []),
+    "    state",
+    `        .forward_bridge_command(app, session_id.as_deref(), \"${tauriCommand}\", ${payload})`,
+    "        .await",
+    "}",
+    "",
+  ];
+}
+
 export type TauriGeneratedPermissionFile = {
   filename: string;
   contents: string;
@@ -511,6 +789,7 @@ export type TauriGeneratedArtifacts = {
   rustContractSource: string;
   rustDispatchSource: string;
   rustInvokeHandlersSource: string;
+  rustCommandsSource: string;
   permissionFiles: TauriGeneratedPermissionFile[];
 };
 
@@ -560,15 +839,28 @@ export function generateTauriArtifacts(): TauriGeneratedArtifacts {
     "// Source: @zakstam/codex-local-component/host/tauri",
     "",
     "macro_rules! bridge_generate_handler {",
-    "    () => {",
+    "    ($($host_command:ident),* $(,)?) => {",
     "        tauri::generate_handler![",
     ...tauriCommands.map((command) => `            ${command.tauriCommand},`),
+    "            $($host_command,)*",
     "        ]",
     "    };",
     "}",
     "",
   ].join("\n")}`;
 
+  // Included into the host crate root, which provides `BridgeHostState` with an
+  // async `forward_bridge_command(app, session_id, tauri_command, payload)` and
+  // the `Validated` wrapper for typed configs.
+  const rustCommandsSource = `${[
+    "// AUTO-GENERATED FILE. DO NOT EDIT.",
+    "// Source: @zakstam/codex-local-component/host/tauri",
+    "",
+    ...tauriCommands.flatMap((command) =>
+      command.forward === undefined ? [] : renderRustForwardCommand(command.tauriCommand, command.forward),
+    ),
+  ].join("\n")}`;
+
   const permissionFiles = tauriCommands
     .filter((command) => command.permission === true)
     .map((command) => {
@@ -596,6 +888,7 @@ export function generateTauriArtifacts(): TauriGeneratedArtifacts {
     rustContractSource,
     rustDispatchSource,
     rustInvokeHandlersSource,
+    rustCommandsSource,
     permissionFiles,
   };
 }
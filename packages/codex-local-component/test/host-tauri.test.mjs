@@ -79,6 +79,53 @@ test("createTauriBridgeClient wraps invoke with expected payload envelopes", asy
   assert.deepEqual(calls[4], { command: "get_bridge_state", args: undefined });
 });
 
+test("createTauriBridgeClient stop forwards optional shutdown deadlines", async () => {
+  const calls = [];
+  const client = createTauriBridgeClient(async (command, args) => {
+    calls.push({ command, args });
+    return { wasRunning: true, timedOutPhase: null };
+  });
+
+  await client.lifecycle.stop();
+  const report = await client.lifecycle.stop({ interruptFirst: false, ackTimeoutMs: 2000 });
+
+  assert.equal(report.timedOutPhase, null);
+  assert.deepEqual(calls[0], { command: "stop_bridge", args: undefined });
+  assert.deepEqual(calls[1], {
+    command: "stop_bridge",
+    args: { config: { interruptFirst: false, ackTimeoutMs: 2000 } },
+  });
+});
+
+test("createTauriBridgeClient sessionId option tags bridge invokes", async () => {
+  const calls = [];
+  const client = createTauriBridgeClient(
+    async (command, args) => {
+      calls.push({ command, args });
+      return command === "list_sessions" ? [] : { ok: true };
+    },
+    { sessionId: "repo-a" },
+  );
+
+  await client.turns.send("hello");
+  await client.lifecycle.getState();
+  await client.sessions.list();
+  await client.sessions.stop("repo-b");
+  await client.events.subscribe({ threadIds: ["thread-1"] });
+  await client.diagnostics.doctor({ skipHandshake: true });
+  await client.diagnostics.export();
+
+  assert.deepEqual(calls, [
+    { command: "send_user_turn", args: { text: "hello", sessionId: "repo-a" } },
+    { command: "get_bridge_state", args: { sessionId: "repo-a" } },
+    { command: "list_sessions", args: undefined },
+    { command: "stop_session", args: { sessionId: "repo-b" } },
+    { command: "subscribe_events", args: { config: { threadIds: ["thread-1"] } } },
+    { command: "bridge_doctor", args: { config: { skipHandshake: true } } },
+    { command: "export_diagnostics", args: undefined },
+  ]);
+});
+
 test("createTauriBridgeClient send keeps fail-fast behavior by default", async () => {
   const calls = [];
   const client = createTauriBridgeClient(async (command, args) => {
@@ -235,6 +282,17 @@ test("generateTauriArtifacts returns command-aligned rust and permission outputs
   assert.match(artifacts.rustContractSource, /pub const BRIDGE_COMMANDS/);
   assert.match(artifacts.rustDispatchSource, /helper_command_for_tauri_command/);
   assert.match(artifacts.rustInvokeHandlersSource, /tauri::generate_handler!/);
+  assert.match(artifacts.rustInvokeHandlersSource, /\$\(\$host_command,\)\*/);
+  assert.match(artifacts.rustCommandsSource, /async fn send_user_turn\(/);
+  assert.match(artifacts.rustCommandsSource, /    text: String,\n/);
+  assert.match(artifacts.rustCommandsSource, /"send_user_turn", serde_json::json!\(\{ "text": text \}\)/);
+  assert.match(artifacts.rustCommandsSource, /    config: Validated<RespondCommandApprovalConfig>,\n/);
+  assert.match(artifacts.rustCommandsSource, /serde_json::to_value\(config\)/);
+  assert.match(artifacts.rustCommandsSource, /"respond_command_approval", payload\)/);
+  assert.match(artifacts.rustCommandsSource, /    config: Validated<SetDisabledToolsConfig>,\n/);
+  assert.match(artifacts.rustCommandsSource, /"set_disabled_tools", payload\)/);
+  assert.doesNotMatch(artifacts.rustCommandsSource, /fn start_bridge\(/);
+  assert.doesNotMatch(artifacts.rustCommandsSource, /fn get_bridge_state\(/);
 
   assert.equal(artifacts.permissionFiles.length, 17);
   const startPermission = artifacts.permissionFiles.find((file) => file.filename === "start_bridge.toml");